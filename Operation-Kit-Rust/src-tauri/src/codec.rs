@@ -0,0 +1,240 @@
+use crate::error::{AppError, AppResult};
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+// 标准base64：编码时补齐'='，解码时有无补齐都接受
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+// URL安全的base64：编码时不补齐，解码时有无补齐都接受
+const BASE64_URL: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+// 编码方式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Codec {
+    Base64,
+    Base64Url,
+    // 百分号编码，除字母数字和 -_.~ 外全部编码；解码时'+'保持不变
+    Url,
+    // \uXXXX 转义，只转义非ASCII字符
+    Unicode,
+    // 小写十六进制
+    Hex,
+}
+
+// 编码还是解码
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodecDirection {
+    Encode,
+    Decode,
+}
+
+// 编解码选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodecOptions {
+    pub codec: Codec,
+    pub direction: CodecDirection,
+    // 逐行处理，否则把整段文本作为一个整体
+    pub per_line: bool,
+}
+
+// 解码失败的行
+#[derive(Debug, Clone, Serialize)]
+pub struct CodecFailure {
+    pub line: usize,
+    pub value: String,
+    pub error: String,
+}
+
+// 编解码结果；失败的行原样保留在输出中
+#[derive(Debug, Clone, Serialize)]
+pub struct CodecReport {
+    pub output: String,
+    pub failures: Vec<CodecFailure>,
+}
+
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn url_decode(text: &str) -> AppResult<String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            // from_str_radix允许正负号，需先确认两个字节都是十六进制数字
+            let byte = text
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| AppError::FormatError(format!("第 {} 个字节处的百分号编码无效", i + 1)))?;
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Ok(String::from_utf8(decoded)?)
+}
+
+fn unicode_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() {
+            escaped.push(c);
+        } else {
+            // BMP以外的字符转为代理对
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                escaped.push_str(&format!("\\u{:04x}", unit));
+            }
+        }
+    }
+    escaped
+}
+
+fn unicode_unescape(text: &str) -> AppResult<String> {
+    let mut units: Vec<u16> = Vec::new();
+    let mut rest = text;
+    while let Some(pos) = rest.find("\\u") {
+        units.extend(rest[..pos].encode_utf16());
+        let hex = rest
+            .get(pos + 2..pos + 6)
+            .filter(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| {
+                AppError::FormatError(format!("无效的转义序列: {}", rest[pos..].chars().take(6).collect::<String>()))
+            })?;
+        units.push(u16::from_str_radix(hex, 16).expect("已检查为十六进制"));
+        rest = &rest[pos + 6..];
+    }
+    units.extend(rest.encode_utf16());
+    String::from_utf16(&units).map_err(|_| AppError::EncodingError("转义序列中有不成对的代理项".to_string()))
+}
+
+// 对一段文本编码或解码
+pub fn transform(text: &str, codec: Codec, direction: CodecDirection) -> AppResult<String> {
+    match (codec, direction) {
+        (Codec::Base64, CodecDirection::Encode) => Ok(BASE64.encode(text)),
+        (Codec::Base64, CodecDirection::Decode) => Ok(String::from_utf8(BASE64.decode(text.trim())?)?),
+        (Codec::Base64Url, CodecDirection::Encode) => Ok(BASE64_URL.encode(text)),
+        (Codec::Base64Url, CodecDirection::Decode) => Ok(String::from_utf8(BASE64_URL.decode(text.trim())?)?),
+        (Codec::Url, CodecDirection::Encode) => Ok(url_encode(text)),
+        (Codec::Url, CodecDirection::Decode) => url_decode(text),
+        (Codec::Unicode, CodecDirection::Encode) => Ok(unicode_escape(text)),
+        (Codec::Unicode, CodecDirection::Decode) => unicode_unescape(text),
+        (Codec::Hex, CodecDirection::Encode) => Ok(hex::encode(text)),
+        (Codec::Hex, CodecDirection::Decode) => {
+            let bytes = hex::decode(text.trim()).map_err(|e| AppError::FormatError(e.to_string()))?;
+            Ok(String::from_utf8(bytes)?)
+        }
+    }
+}
+
+// 批量编解码：逐行处理时空行保留，失败的行原样保留并报告
+pub fn transform_text(input: &str, options: &CodecOptions) -> AppResult<CodecReport> {
+    if input.is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+    if !options.per_line {
+        return Ok(CodecReport {
+            output: transform(input, options.codec, options.direction)?,
+            failures: Vec::new(),
+        });
+    }
+
+    let mut lines = Vec::new();
+    let mut failures = Vec::new();
+    for (i, line) in input.split('\n').enumerate() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+        match transform(line, options.codec, options.direction) {
+            Ok(value) => lines.push(value),
+            Err(e) => {
+                failures.push(CodecFailure {
+                    line: i + 1,
+                    value: line.to_string(),
+                    error: e.to_string(),
+                });
+                lines.push(line.to_string());
+            }
+        }
+    }
+
+    Ok(CodecReport {
+        output: lines.join("\n"),
+        failures,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips() {
+        let text = "app://open?uid=123&name=张三 😀";
+        for codec in [Codec::Base64, Codec::Base64Url, Codec::Url, Codec::Unicode, Codec::Hex] {
+            let encoded = transform(text, codec, CodecDirection::Encode).unwrap();
+            assert_eq!(transform(&encoded, codec, CodecDirection::Decode).unwrap(), text, "{:?}", codec);
+        }
+    }
+
+    #[test]
+    fn test_known_values() {
+        assert_eq!(transform("a?b", Codec::Base64Url, CodecDirection::Encode).unwrap(), "YT9i");
+        assert_eq!(transform("YWI", Codec::Base64, CodecDirection::Decode).unwrap(), "ab");
+        assert_eq!(transform("a b/中", Codec::Url, CodecDirection::Encode).unwrap(), "a%20b%2F%E4%B8%AD");
+        assert_eq!(transform("中", Codec::Unicode, CodecDirection::Encode).unwrap(), "\\u4e2d");
+        assert_eq!(transform("hi", Codec::Hex, CodecDirection::Encode).unwrap(), "6869");
+    }
+
+    #[test]
+    fn test_per_line_failures() {
+        let options = CodecOptions {
+            codec: Codec::Base64,
+            direction: CodecDirection::Decode,
+            per_line: true,
+        };
+        let report = transform_text("YWI=\n\n!!!\nYw==", &options).unwrap();
+        assert_eq!(report.output, "ab\n\n!!!\nc");
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].line, 3);
+
+        let whole = CodecOptions { per_line: false, ..options };
+        assert!(transform_text("!!!", &whole).is_err());
+    }
+
+    #[test]
+    fn test_malformed_escapes() {
+        // 截断的转义序列以多字节字符结尾时应报错而不是panic
+        let err = transform("\\u12中", Codec::Unicode, CodecDirection::Decode).unwrap_err();
+        assert!(err.to_string().contains("\\u12中"));
+        assert!(transform("%+1", Codec::Url, CodecDirection::Decode).is_err());
+        assert!(transform("%-1", Codec::Url, CodecDirection::Decode).is_err());
+    }
+}
@@ -1,8 +1,33 @@
 use crate::{
-    crypto::{decrypt_text, encrypt_number, get_current_config, set_config, CryptoConfig, init_config, use_huawei_prefix, set_use_huawei_prefix},
-    text_processor::{add_quotes, convert_format, remove_quotes, replace_chinese_commas},
-    oss::{upload_ids_to_oss, Channel},
+    crypto::{decrypt_batch, encrypt_batch, get_current_config, set_config, CryptoConfig, init_config, use_huawei_prefix, set_use_huawei_prefix},
+    text_processor::{add_quotes, convert_format, remove_quotes, replace_chinese_commas, QuoteOptions},
+    oss::Channel,
+    storage::{upload_ids, Backend, ObjectInfo, StorageBackend},
+    vault::{
+        delete_credential, find_credential, list_credentials, lock_vault, save_credential, unlock_vault,
+        vault_status, Credential, CredentialSummary, VaultStatus,
+    },
+    target::{delete_target, find_target, load_targets, save_target, OssTarget},
+    sql::{generate_sql, SqlOptions},
+    recipe::{delete_recipe, find_recipe, load_recipes, run_recipe, save_recipe, Recipe, RecipeReport},
+    table::{extract_column, transform_column, ColumnOp, TableOptions},
+    precision::{check_precision, PrecisionReport},
+    sanitize::{sanitize_report, sanitize_text, SanitizeReport},
+    extract::{extract_ids, replace_in_place, ExtractOptions, InPlaceOptions, InPlaceReport},
+    encoding::{read_text_file, write_text_file, DecodedText, TextEncoding},
+    mask::{mask_list, MaskOptions},
+    split::{split_to_files, SplitOptions, SplitReport},
+    codec::{transform_text, CodecOptions, CodecReport},
+    radix::{convert_radix_list, RadixOptions, RadixReport},
+    snowflake::{decode_snowflakes, SnowflakeOptions, SnowflakeReport},
+    timestamp::{convert_timestamps, TimestampOptions, TimestampReport},
+    template::{render_template, TemplateOptions},
+    stats::{list_stats, ListStats},
+    phone::{normalize_phones_report, PhoneOptions, PhoneReport},
+    validate::{validate_list, ValidationReport, Validator},
+    xlsx::{list_sheets, read_column, write_columns, XlsxColumn, XlsxImportOptions},
 };
+use std::path::{Path, PathBuf};
 
 // 初始化配置（在应用启动时调用）
 pub fn init_crypto_config() {
@@ -32,48 +57,82 @@ pub async fn set_crypto_config(config_name: String) -> Result<(), String> {
     Ok(())
 }
 
+// 批量加密，默认先清理零宽空格、BOM等不可见字符；末尾全为0的长ID默认拒绝
 #[tauri::command]
-pub async fn process_batch_encrypt(input: String) -> Result<String, String> {
-    let items: Vec<&str> = input
-        .split(|c| c == '\n' || c == ',')
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .collect();
+pub async fn process_batch_encrypt(
+    input: String,
+    sanitize: Option<bool>,
+    allow_trailing_zeros: Option<bool>,
+) -> Result<String, String> {
+    let input = if sanitize.unwrap_or(true) { sanitize_text(&input) } else { input };
+    encrypt_batch(&input, allow_trailing_zeros.unwrap_or(false)).map_err(|e| e.to_string())
+}
 
-    let uses_newlines = input.matches('\n').count() > input.matches(',').count();
-    let delimiter = if uses_newlines { "\n" } else { "," };
+// 批量解密，默认先清理零宽空格、BOM等不可见字符
+#[tauri::command]
+pub async fn process_batch_decrypt(input: String, sanitize: Option<bool>) -> Result<String, String> {
+    let input = if sanitize.unwrap_or(true) { sanitize_text(&input) } else { input };
+    decrypt_batch(&input).map_err(|e| e.to_string())
+}
 
-    let mut result = Vec::with_capacity(items.len());
-    for item in items {
-        match encrypt_number(item) {
-            Ok(encrypted) => result.push(encrypted),
-            Err(e) => return Err(e.to_string()),
-        }
-    }
+// 解析雪花ID的生成时间、数据中心、机器和序列号
+#[tauri::command]
+pub async fn process_decode_snowflake(input: String, options: Option<SnowflakeOptions>) -> Result<SnowflakeReport, String> {
+    decode_snowflakes(&input, &options.unwrap_or_default()).map_err(|e| e.to_string())
+}
 
-    Ok(result.join(delimiter))
+// 在十进制、二进制、十六进制、36/62进制和自定义字母表之间转换，超出u64的数值不丢精度
+#[tauri::command]
+pub async fn process_convert_radix(input: String, options: RadixOptions) -> Result<RadixReport, String> {
+    convert_radix_list(&input, &options).map_err(|e| e.to_string())
 }
 
+// 清理不可见字符，并报告每行发现的字符
 #[tauri::command]
-pub async fn process_batch_decrypt(input: String) -> Result<String, String> {
-    let items: Vec<&str> = input
-        .split(|c| c == '\n' || c == ',')
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .collect();
+pub async fn process_sanitize(input: String) -> Result<SanitizeReport, String> {
+    Ok(sanitize_report(&input))
+}
 
-    let uses_newlines = input.matches('\n').count() > input.matches(',').count();
-    let delimiter = if uses_newlines { "\n" } else { "," };
+// 从聊天记录、日志、JSON等任意文本中提取ID
+#[tauri::command]
+pub async fn process_extract_ids(input: String, options: ExtractOptions) -> Result<String, String> {
+    extract_ids(&input, &options).map_err(|e| e.to_string())
+}
 
-    let mut result = Vec::with_capacity(items.len());
-    for item in items {
-        match decrypt_text(item) {
-            Ok(decrypted) => result.push(decrypted),
-            Err(e) => return Err(e.to_string()),
-        }
-    }
+// 保持原文不变，只把其中的ID原位加密或解密
+#[tauri::command]
+pub async fn process_replace_in_place(input: String, options: InPlaceOptions) -> Result<InPlaceReport, String> {
+    replace_in_place(&input, &options).map_err(|e| e.to_string())
+}
 
-    Ok(result.join(delimiter))
+// 按指定规则逐行校验ID，返回每行结果和只含有效值的输出
+#[tauri::command]
+pub async fn process_validate_ids(input: String, validator: Validator) -> Result<ValidationReport, String> {
+    validate_list(&input, &validator).map_err(|e| e.to_string())
+}
+
+// 把手机号规范化为11位或E.164格式，报告无法处理的号码
+#[tauri::command]
+pub async fn process_normalize_phones(input: String, options: Option<PhoneOptions>) -> Result<PhoneReport, String> {
+    normalize_phones_report(&input, &options.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+// 对手机号、身份证、邮箱、姓名等敏感信息脱敏
+#[tauri::command]
+pub async fn process_mask(input: String, options: Option<MaskOptions>) -> Result<String, String> {
+    mask_list(&input, &options.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+// 统计条目数、重复、空行和非数字条目，上传前检查数据质量
+#[tauri::command]
+pub async fn process_list_stats(input: String) -> Result<ListStats, String> {
+    list_stats(&input).map_err(|e| e.to_string())
+}
+
+// 检查被Excel改成科学计数法或截断精度的ID
+#[tauri::command]
+pub async fn process_check_precision(input: String) -> Result<PrecisionReport, String> {
+    check_precision(&input).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -87,13 +146,172 @@ pub async fn process_replace_commas(input: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-pub async fn process_add_quotes(input: String) -> Result<String, String> {
-    add_quotes(&input).map_err(|e| e.to_string())
+pub async fn process_add_quotes(input: String, options: Option<QuoteOptions>) -> Result<String, String> {
+    add_quotes(&input, &options.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn process_remove_quotes(input: String, options: Option<QuoteOptions>) -> Result<String, String> {
+    remove_quotes(&input, &options.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+// 将ID列表生成为SQL（IN子句、VALUES或UNION ALL临时表）
+#[tauri::command]
+pub async fn process_generate_sql(input: String, options: SqlOptions) -> Result<String, String> {
+    generate_sql(&input, &options).map_err(|e| e.to_string())
+}
+
+// 对每个条目渲染一次模板，生成命令、SQL或JSON行
+#[tauri::command]
+pub async fn process_render_template(input: String, options: TemplateOptions) -> Result<String, String> {
+    render_template(&input, &options).map_err(|e| e.to_string())
+}
+
+// base64/URL/Unicode转义/十六进制编解码，逐行处理时报告失败的行
+#[tauri::command]
+pub async fn process_codec(input: String, options: CodecOptions) -> Result<CodecReport, String> {
+    transform_text(&input, &options).map_err(|e| e.to_string())
+}
+
+// 时间戳（自动识别秒/毫秒/微秒）与指定时区的格式化时间互转
+#[tauri::command]
+pub async fn process_timestamps(input: String, options: Option<TimestampOptions>) -> Result<TimestampReport, String> {
+    convert_timestamps(&input, &options.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+// 从CSV/TSV中提取指定列，每行一个值
+#[tauri::command]
+pub async fn process_extract_column(input: String, options: TableOptions) -> Result<String, String> {
+    extract_column(&input, &options).map_err(|e| e.to_string())
+}
+
+// 对CSV/TSV的指定列执行加密/解密/引号处理，并写回整张表
+#[tauri::command]
+pub async fn process_transform_column(input: String, options: TableOptions, op: ColumnOp) -> Result<String, String> {
+    transform_column(&input, &options, &op).map_err(|e| e.to_string())
+}
+
+// 读取文本文件（txt/csv/tsv），自动识别UTF-8/UTF-16/GBK编码
+#[tauri::command]
+pub async fn import_text_file(path: String) -> Result<DecodedText, String> {
+    read_text_file(Path::new(&path)).map_err(|e| e.to_string())
+}
+
+// 按指定编码导出文本文件
+#[tauri::command]
+pub async fn export_text_file(path: String, content: String, encoding: TextEncoding) -> Result<(), String> {
+    write_text_file(Path::new(&path), &content, encoding).map_err(|e| e.to_string())
+}
+
+// 按行数或份数拆分列表，写入目标文件夹或打包为zip
+#[tauri::command]
+pub async fn export_split_files(input: String, dir: String, options: SplitOptions) -> Result<SplitReport, String> {
+    split_to_files(&input, Path::new(&dir), &options).map_err(|e| e.to_string())
+}
+
+// 列出xlsx文件中的工作表
+#[tauri::command]
+pub async fn list_xlsx_sheets(path: String) -> Result<Vec<String>, String> {
+    list_sheets(Path::new(&path)).map_err(|e| e.to_string())
 }
 
+// 从xlsx文件读取指定工作表的指定列
 #[tauri::command]
-pub async fn process_remove_quotes(input: String) -> Result<String, String> {
-    remove_quotes(&input).map_err(|e| e.to_string())
+pub async fn import_xlsx_column(path: String, options: XlsxImportOptions) -> Result<String, String> {
+    read_column(Path::new(&path), &options).map_err(|e| e.to_string())
+}
+
+// 将多列结果导出为xlsx文件
+#[tauri::command]
+pub async fn export_xlsx(path: String, sheet: String, columns: Vec<XlsxColumn>) -> Result<(), String> {
+    write_columns(Path::new(&path), &sheet, &columns).map_err(|e| e.to_string())
+}
+
+// 获取应用数据目录，用于保存配方等本地数据
+fn app_data_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path_resolver()
+        .app_data_dir()
+        .ok_or_else(|| "无法获取应用数据目录".to_string())
+}
+
+// 获取所有已保存的配方
+#[tauri::command]
+pub async fn list_recipes(app: tauri::AppHandle) -> Result<Vec<Recipe>, String> {
+    load_recipes(&app_data_dir(&app)?).map_err(|e| e.to_string())
+}
+
+// 保存配方（同名覆盖）
+#[tauri::command]
+pub async fn save_recipe_config(app: tauri::AppHandle, recipe: Recipe) -> Result<(), String> {
+    save_recipe(&app_data_dir(&app)?, recipe).map_err(|e| e.to_string())
+}
+
+// 删除配方
+#[tauri::command]
+pub async fn delete_recipe_config(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    delete_recipe(&app_data_dir(&app)?, &name).map_err(|e| e.to_string())
+}
+
+// 按名称执行配方，返回结果和每一步的条目数
+#[tauri::command]
+pub async fn process_run_recipe(app: tauri::AppHandle, name: String, input: String) -> Result<RecipeReport, String> {
+    let recipe = find_recipe(&app_data_dir(&app)?, &name).map_err(|e| e.to_string())?;
+    run_recipe(&recipe, &input).map_err(|e| e.to_string())
+}
+
+// 获取所有OSS上传目标
+#[tauri::command]
+pub async fn list_oss_targets(app: tauri::AppHandle) -> Result<Vec<OssTarget>, String> {
+    load_targets(&app_data_dir(&app)?).map_err(|e| e.to_string())
+}
+
+// 保存OSS上传目标（同名覆盖）
+#[tauri::command]
+pub async fn save_oss_target(app: tauri::AppHandle, target: OssTarget) -> Result<(), String> {
+    save_target(&app_data_dir(&app)?, target).map_err(|e| e.to_string())
+}
+
+// 删除OSS上传目标
+#[tauri::command]
+pub async fn delete_oss_target(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    delete_target(&app_data_dir(&app)?, &name).map_err(|e| e.to_string())
+}
+
+// 获取凭证保险库是否已创建、是否已解锁
+#[tauri::command]
+pub async fn get_vault_status(app: tauri::AppHandle) -> Result<VaultStatus, String> {
+    Ok(vault_status(&app_data_dir(&app)?))
+}
+
+// 用主密码解锁保险库，第一次使用时以该密码新建
+#[tauri::command]
+pub async fn unlock_credential_vault(app: tauri::AppHandle, password: String) -> Result<(), String> {
+    unlock_vault(&app_data_dir(&app)?, &password).map_err(|e| e.to_string())
+}
+
+// 锁定保险库
+#[tauri::command]
+pub async fn lock_credential_vault() -> Result<(), String> {
+    lock_vault();
+    Ok(())
+}
+
+// 获取已保存的凭证（不含密钥）
+#[tauri::command]
+pub async fn list_saved_credentials(app: tauri::AppHandle) -> Result<Vec<CredentialSummary>, String> {
+    list_credentials(&app_data_dir(&app)?).map_err(|e| e.to_string())
+}
+
+// 保存凭证（同名覆盖）
+#[tauri::command]
+pub async fn save_credential_config(app: tauri::AppHandle, credential: Credential) -> Result<(), String> {
+    save_credential(&app_data_dir(&app)?, credential).map_err(|e| e.to_string())
+}
+
+// 删除凭证
+#[tauri::command]
+pub async fn delete_credential_config(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    delete_credential(&app_data_dir(&app)?, &name).map_err(|e| e.to_string())
 }
 
 // 获取当前华为前缀配置
@@ -109,30 +327,113 @@ pub async fn set_huawei_prefix_config(use_prefix: bool) -> Result<(), String> {
     Ok(())
 }
 
-// 将ID列表上传到OSS
+// 按名称打开上传目标的存储后端；OSS和S3从已解锁的保险库中取出凭证，前端不接触密钥
+// 返回后端、目标和STS凭证即将过期的提醒
+fn open_backend(
+    app: &tauri::AppHandle,
+    credential: Option<String>,
+    target: &str,
+) -> Result<(Backend, OssTarget, Option<String>), String> {
+    let dir = app_data_dir(app)?;
+    let target = find_target(&dir, target).map_err(|e| e.to_string())?;
+    let (secret, warning) = match credential.filter(|name| !name.is_empty()) {
+        Some(name) => {
+            let credential = find_credential(&dir, &name).map_err(|e| e.to_string())?;
+            // STS凭证过期时直接拒绝，即将过期时在结果中提醒
+            let warning = credential
+                .secret
+                .check_expiry(chrono::Utc::now())
+                .map_err(|e| e.to_string())?;
+            (Some(credential.secret), warning)
+        }
+        None => (None, None),
+    };
+    let backend = Backend::open(&target, secret).map_err(|e| e.to_string())?;
+    Ok((backend, target, warning))
+}
+
+// 将ID列表上传到目标存储（OSS、S3兼容存储或本地文件夹）；末尾全为0的长ID默认拒绝
 #[tauri::command]
-pub async fn upload_to_oss(access_id: String, access_key: String, content: String, channel: String) -> Result<String, String> {
+pub async fn upload_to_storage(
+    app: tauri::AppHandle,
+    credential: Option<String>,
+    content: String,
+    channel: String,
+    target: String,
+    allow_trailing_zeros: Option<bool>,
+) -> Result<String, String> {
     // 转换渠道名称为枚举类型
     let channel = Channel::from_str(&channel).map_err(|e| e.to_string())?;
+    let (backend, target, warning) = open_backend(&app, credential, &target)?;
     
-    // 调用OSS模块上传内容
-    upload_ids_to_oss(&access_id, &access_key, &content, channel)
+    let message = upload_ids(&backend, &target, &content, channel, allow_trailing_zeros.unwrap_or(false))
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    Ok(match warning {
+        Some(warning) => format!("{}（{}）", message, warning),
+        None => message,
+    })
+}
+
+// 列出目标中以prefix开头的对象
+#[tauri::command]
+pub async fn list_storage_objects(
+    app: tauri::AppHandle,
+    credential: Option<String>,
+    target: String,
+    prefix: String,
+) -> Result<Vec<ObjectInfo>, String> {
+    let (backend, _, _) = open_backend(&app, credential, &target)?;
+    backend.list(&prefix).await.map_err(|e| e.to_string())
+}
+
+// 读取目标中的对象，按文本返回
+#[tauri::command]
+pub async fn read_storage_object(
+    app: tauri::AppHandle,
+    credential: Option<String>,
+    target: String,
+    key: String,
+) -> Result<String, String> {
+    let (backend, _, _) = open_backend(&app, credential, &target)?;
+    let bytes = backend.get(&key).await.map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|_| format!("{} 不是UTF-8文本", key))
+}
+
+// 删除目标中的对象
+#[tauri::command]
+pub async fn delete_storage_object(
+    app: tauri::AppHandle,
+    credential: Option<String>,
+    target: String,
+    key: String,
+) -> Result<(), String> {
+    let (backend, _, _) = open_backend(&app, credential, &target)?;
+    backend.delete(&key).await.map_err(|e| e.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::TEST_CONFIG_LOCK;
 
     #[tokio::test]
     async fn test_batch_encrypt_decrypt() {
+        let _guard = TEST_CONFIG_LOCK.lock().await;
         let input = "12345,67890";
-        let encrypted = process_batch_encrypt(input.to_string()).await.unwrap();
-        let decrypted = process_batch_decrypt(encrypted).await.unwrap();
+        let encrypted = process_batch_encrypt(input.to_string(), None, None).await.unwrap();
+        let decrypted = process_batch_decrypt(encrypted, None).await.unwrap();
         assert_eq!(input, decrypted);
     }
 
+    #[tokio::test]
+    async fn test_batch_encrypt_sanitizes_by_default() {
+        let _guard = TEST_CONFIG_LOCK.lock().await;
+        let input = "\u{FEFF}12345\u{200B}\r\n67890";
+        assert!(process_batch_encrypt(input.to_string(), None, None).await.is_ok());
+        assert!(process_batch_encrypt(input.to_string(), Some(false), None).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_format_conversion() {
         let input = "1,2,3";
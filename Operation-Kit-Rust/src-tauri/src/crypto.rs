@@ -1,4 +1,6 @@
 use crate::error::{AppError, AppResult};
+use crate::precision::repair_precision;
+use crate::text_processor::{split_items, uses_newlines};
 use harsh::Harsh;
 use std::sync::{Mutex, OnceLock};
 
@@ -30,6 +32,14 @@ impl CryptoConfig {
             CryptoConfig::Huawei => Some("abcdefghijklmnopqrstuvwxyz1234567890"),
         }
     }
+
+    // 英文标识，用于对象路径等
+    pub fn key(&self) -> &'static str {
+        match self {
+            CryptoConfig::General => "general",
+            CryptoConfig::Huawei => "huawei",
+        }
+    }
 }
 
 // 全局配置 - 使用Mutex实现内部可变性
@@ -41,6 +51,17 @@ const HUAWEI_PREFIX: &str = "haot";
 // 是否在华为模式下使用前缀 - 默认为true
 static USE_HUAWEI_PREFIX: OnceLock<Mutex<bool>> = OnceLock::new();
 
+// 测试会切换全局配置，切换配置或依赖当前配置的测试都要先持有这把锁，避免并行执行时互相干扰
+// 用tokio的Mutex，异步测试可以跨await持有；测试失败时也不会中毒
+#[cfg(test)]
+pub static TEST_CONFIG_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+// 同步测试中获取配置锁
+#[cfg(test)]
+pub fn lock_config_for_test() -> tokio::sync::MutexGuard<'static, ()> {
+    TEST_CONFIG_LOCK.blocking_lock()
+}
+
 // 初始化配置
 pub fn init_config() {
     CURRENT_CONFIG.get_or_init(|| Mutex::new(CryptoConfig::General));
@@ -259,12 +280,57 @@ pub fn decrypt_text(text: &str) -> AppResult<String> {
     }
 }
 
+// 判断文本片段是否为当前配置生成的加密串：长度和字母表符合，并且能成功解密
+pub fn is_profile_hash(token: &str) -> bool {
+    let config = get_current_config();
+    if token.len() < config.min_length() || token.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    if let Some(alphabet) = config.alphabet() {
+        if !token.chars().all(|c| alphabet.contains(c)) {
+            return false;
+        }
+    }
+    decrypt_text(token).is_ok()
+}
+
+// 判断文本片段是否带有当前配置的加密前缀（华为模式的haot），用于识别无法解密的加密串
+pub fn has_profile_prefix(token: &str) -> bool {
+    get_current_config() == CryptoConfig::Huawei
+        && use_huawei_prefix()
+        && token.len() > HUAWEI_PREFIX.len()
+        && token.starts_with(HUAWEI_PREFIX)
+}
+
+// 批量加密：按输入的分隔符拆分，逐项加密后用相同的分隔符拼接
+// 加密前先展开被Excel改成科学计数法的ID，无法还原或疑似被截断的拒绝，避免加密错误的数字
+pub fn encrypt_batch(input: &str, allow_trailing_zeros: bool) -> AppResult<String> {
+    let input = repair_precision(input, allow_trailing_zeros)?;
+    let delimiter = if uses_newlines(&input) { "\n" } else { "," };
+    let result = split_items(&input)
+        .into_iter()
+        .map(encrypt_number)
+        .collect::<AppResult<Vec<String>>>()?;
+    Ok(result.join(delimiter))
+}
+
+// 批量解密：按输入的分隔符拆分，逐项解密后用相同的分隔符拼接
+pub fn decrypt_batch(input: &str) -> AppResult<String> {
+    let delimiter = if uses_newlines(input) { "\n" } else { "," };
+    let result = split_items(input)
+        .into_iter()
+        .map(decrypt_text)
+        .collect::<AppResult<Vec<String>>>()?;
+    Ok(result.join(delimiter))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_specific_short_hash() {
+        let _guard = lock_config_for_test();
         // 保存原始设置
         let original_config = get_current_config();
         
@@ -298,6 +364,7 @@ mod tests {
     
     #[test]
     fn test_another_short_hash() {
+        let _guard = lock_config_for_test();
         // 保存原始设置
         let original_config = get_current_config();
         
@@ -322,6 +389,7 @@ mod tests {
     
     #[test]
     fn test_long_hash() {
+        let _guard = lock_config_for_test();
         // 保存原始设置
         let original_config = get_current_config();
         
@@ -0,0 +1,176 @@
+use crate::error::{AppError, AppResult};
+use encoding_rs::{Encoding, GB18030, GBK, UTF_16BE, UTF_16LE, UTF_8};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+// 文本文件编码
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextEncoding {
+    Utf8,
+    // 带BOM的UTF-8，Windows Excel打开CSV时能正确识别中文
+    Utf8Bom,
+    // 带BOM的UTF-16LE，Excel“Unicode文本”格式
+    Utf16Le,
+    Utf16Be,
+    // GBK/GB18030，中文Windows默认编码
+    Gbk,
+}
+
+impl TextEncoding {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf8Bom => "UTF-8 (BOM)",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+            TextEncoding::Gbk => "GBK",
+        }
+    }
+}
+
+// 导入结果：解码后的文本和识别出的编码
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedText {
+    pub content: String,
+    pub encoding: TextEncoding,
+}
+
+// 没有BOM时，根据0字节出现在奇数位还是偶数位判断是否为UTF-16
+fn sniff_utf16(bytes: &[u8]) -> Option<TextEncoding> {
+    if bytes.len() < 2 || !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let sample = &bytes[..bytes.len().min(4096)];
+    let pairs = sample.len() / 2;
+    let even_zeros = sample.iter().step_by(2).filter(|b| **b == 0).count();
+    let odd_zeros = sample.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+    // ASCII为主的UTF-16文本中，约一半字节是0
+    if odd_zeros * 2 > pairs && even_zeros == 0 {
+        Some(TextEncoding::Utf16Le)
+    } else if even_zeros * 2 > pairs && odd_zeros == 0 {
+        Some(TextEncoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+fn decode_with(encoding: &'static Encoding, bytes: &[u8], label: &str) -> AppResult<String> {
+    let (content, had_errors) = encoding.decode_without_bom_handling(bytes);
+    if had_errors {
+        return Err(AppError::EncodingError(format!("无法按{}解码文件内容", label)));
+    }
+    Ok(content.into_owned())
+}
+
+// 识别编码并解码：BOM > 无BOM的UTF-16 > 合法UTF-8 > GB18030
+pub fn decode_bytes(bytes: &[u8]) -> AppResult<DecodedText> {
+    let (encoding, body) = if let Some(body) = bytes.strip_prefix(UTF8_BOM) {
+        (TextEncoding::Utf8Bom, body)
+    } else if let Some(body) = bytes.strip_prefix(UTF16LE_BOM) {
+        (TextEncoding::Utf16Le, body)
+    } else if let Some(body) = bytes.strip_prefix(UTF16BE_BOM) {
+        (TextEncoding::Utf16Be, body)
+    } else if let Some(encoding) = sniff_utf16(bytes) {
+        // 含大量0字节的UTF-16也是合法的UTF-8，必须先判断
+        (encoding, bytes)
+    } else if std::str::from_utf8(bytes).is_ok() {
+        (TextEncoding::Utf8, bytes)
+    } else {
+        (TextEncoding::Gbk, bytes)
+    };
+
+    let content = match encoding {
+        TextEncoding::Utf8 | TextEncoding::Utf8Bom => decode_with(UTF_8, body, encoding.label())?,
+        TextEncoding::Utf16Le => decode_with(UTF_16LE, body, encoding.label())?,
+        TextEncoding::Utf16Be => decode_with(UTF_16BE, body, encoding.label())?,
+        // GB18030是GBK的超集，解码时统一按GB18030处理
+        TextEncoding::Gbk => decode_with(GB18030, body, "GBK/GB18030")?,
+    };
+
+    Ok(DecodedText { content, encoding })
+}
+
+// 按指定编码编码文本；GBK无法表示的字符会报错，而不是静默替换
+pub fn encode_text(content: &str, encoding: TextEncoding) -> AppResult<Vec<u8>> {
+    match encoding {
+        TextEncoding::Utf8 => Ok(content.as_bytes().to_vec()),
+        TextEncoding::Utf8Bom => {
+            let mut bytes = UTF8_BOM.to_vec();
+            bytes.extend_from_slice(content.as_bytes());
+            Ok(bytes)
+        }
+        TextEncoding::Utf16Le => {
+            let mut bytes = UTF16LE_BOM.to_vec();
+            bytes.extend(content.encode_utf16().flat_map(u16::to_le_bytes));
+            Ok(bytes)
+        }
+        TextEncoding::Utf16Be => {
+            let mut bytes = UTF16BE_BOM.to_vec();
+            bytes.extend(content.encode_utf16().flat_map(u16::to_be_bytes));
+            Ok(bytes)
+        }
+        TextEncoding::Gbk => {
+            let (bytes, _, had_unmappable) = GBK.encode(content);
+            if had_unmappable {
+                return Err(AppError::EncodingError(
+                    "内容中有GBK无法表示的字符，请改用UTF-8 (BOM)导出".to_string(),
+                ));
+            }
+            Ok(bytes.into_owned())
+        }
+    }
+}
+
+// 读取文本文件并自动识别编码
+pub fn read_text_file(path: &Path) -> AppResult<DecodedText> {
+    let bytes = fs::read(path)?;
+    decode_bytes(&bytes)
+}
+
+// 按指定编码写入文本文件
+pub fn write_text_file(path: &Path, content: &str, encoding: TextEncoding) -> AppResult<()> {
+    let bytes = encode_text(content, encoding)?;
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_encodings() {
+        let text = "用户ID\n12345";
+        for encoding in [
+            TextEncoding::Utf8,
+            TextEncoding::Utf8Bom,
+            TextEncoding::Utf16Le,
+            TextEncoding::Utf16Be,
+            TextEncoding::Gbk,
+        ] {
+            let bytes = encode_text(text, encoding).unwrap();
+            let decoded = decode_bytes(&bytes).unwrap();
+            assert_eq!(decoded.content, text);
+            assert_eq!(decoded.encoding, encoding);
+        }
+    }
+
+    #[test]
+    fn test_utf16_without_bom() {
+        let bytes: Vec<u8> = "1234\n5678".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let decoded = decode_bytes(&bytes).unwrap();
+        assert_eq!(decoded.encoding, TextEncoding::Utf16Le);
+        assert_eq!(decoded.content, "1234\n5678");
+    }
+
+    #[test]
+    fn test_gbk_unmappable() {
+        assert!(encode_text("😀", TextEncoding::Gbk).is_err());
+    }
+}
@@ -13,11 +13,17 @@ pub enum AppError {
     
     #[error("参数错误: {0}")]
     InvalidInput(String),
+
+    #[error("文件读写错误: {0}")]
+    IoError(String),
+
+    #[error("编码错误: {0}")]
+    EncodingError(String),
 }
 
 impl From<std::string::FromUtf8Error> for AppError {
     fn from(err: std::string::FromUtf8Error) -> Self {
-        AppError::DecryptError(err.to_string())
+        AppError::EncodingError(err.to_string())
     }
 }
 
@@ -27,4 +33,22 @@ impl From<base64::DecodeError> for AppError {
     }
 }
 
+impl From<std::io::Error> for AppError {
+    fn from(err: std::io::Error) -> Self {
+        AppError::IoError(err.to_string())
+    }
+}
+
+impl From<zip::result::ZipError> for AppError {
+    fn from(err: zip::result::ZipError) -> Self {
+        AppError::IoError(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(err: serde_json::Error) -> Self {
+        AppError::FormatError(err.to_string())
+    }
+}
+
 pub type AppResult<T> = Result<T, AppError>; 
\ No newline at end of file
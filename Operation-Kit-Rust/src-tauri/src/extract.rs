@@ -0,0 +1,365 @@
+use crate::crypto::{decrypt_text, encrypt_number, has_profile_prefix, is_profile_hash};
+use crate::error::{AppError, AppResult};
+use crate::validate::mobile_carrier;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+static DIGIT_RUN: OnceLock<Regex> = OnceLock::new();
+static ALNUM_RUN: OnceLock<Regex> = OnceLock::new();
+static UUID: OnceLock<Regex> = OnceLock::new();
+
+fn digit_run() -> &'static Regex {
+    DIGIT_RUN.get_or_init(|| Regex::new(r"[0-9]+").expect("无效的正则表达式"))
+}
+
+// 独立的数字串：前后不能紧邻字母或数字，避免改写 abc123、v2、哈希值中的数字
+// regex不支持环视，这里检查匹配两侧的字节；中文等非ASCII字符相邻时仍算独立
+fn standalone_digit_runs(text: &str) -> impl Iterator<Item = regex::Match<'_>> {
+    let bytes = text.as_bytes();
+    digit_run().find_iter(text).filter(move |m| {
+        let before = m.start().checked_sub(1).map(|i| bytes[i]);
+        let after = bytes.get(m.end()).copied();
+        !before.is_some_and(|b| b.is_ascii_alphanumeric()) && !after.is_some_and(|b| b.is_ascii_alphanumeric())
+    })
+}
+
+fn alnum_run() -> &'static Regex {
+    ALNUM_RUN.get_or_init(|| Regex::new(r"[A-Za-z0-9]+").expect("无效的正则表达式"))
+}
+
+fn uuid_regex() -> &'static Regex {
+    // (?-u:\b) 使用ASCII单词边界，中文和ID相邻时也能匹配
+    UUID.get_or_init(|| {
+        Regex::new(r"(?-u:\b)[0-9A-Fa-f]{8}-[0-9A-Fa-f]{4}-[0-9A-Fa-f]{4}-[0-9A-Fa-f]{4}-[0-9A-Fa-f]{12}(?-u:\b)")
+            .expect("无效的正则表达式")
+    })
+}
+
+// 内置和自定义的提取规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum ExtractPattern {
+    // 指定长度范围的纯数字ID
+    DigitIds { min_length: usize, max_length: usize },
+    // 中国大陆手机号（按已分配号段识别），+86/86前缀会被去掉
+    Mobile,
+    // 当前加密配置生成的加密串
+    Hash,
+    // 8-4-4-4-12 格式的UUID/IDFA
+    Uuid,
+    // OAID：UUID格式，或16/64位十六进制
+    Oaid,
+    // 自定义正则；有捕获组时取第一个捕获组
+    Custom { pattern: String },
+}
+
+// 一个匹配：在原文中的字节范围和提取出的值
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: String,
+}
+
+impl ExtractPattern {
+    // 在文本中查找所有匹配
+    pub fn find(&self, text: &str) -> AppResult<Vec<Match>> {
+        let whole = |m: regex::Match| Match {
+            start: m.start(),
+            end: m.end(),
+            value: m.as_str().to_string(),
+        };
+
+        let matches = match self {
+            ExtractPattern::DigitIds { min_length, max_length } => {
+                if min_length > max_length || *min_length == 0 {
+                    return Err(AppError::InvalidInput("ID长度范围无效".to_string()));
+                }
+                standalone_digit_runs(text)
+                    .filter(|m| (*min_length..=*max_length).contains(&m.len()))
+                    .map(whole)
+                    .collect()
+            }
+            ExtractPattern::Mobile => standalone_digit_runs(text)
+                .filter_map(|m| {
+                    let digits = m.as_str();
+                    let offset = match digits.len() {
+                        11 => 0,
+                        13 if digits.starts_with("86") => 2,
+                        15 if digits.starts_with("0086") => 4,
+                        _ => return None,
+                    };
+                    let number = &digits[offset..];
+                    mobile_carrier(number).is_some().then(|| Match {
+                        start: m.start() + offset,
+                        end: m.end(),
+                        value: number.to_string(),
+                    })
+                })
+                .collect(),
+            ExtractPattern::Hash => alnum_run()
+                .find_iter(text)
+                .filter(|m| is_profile_hash(m.as_str()))
+                .map(whole)
+                .collect(),
+            ExtractPattern::Uuid => uuid_regex().find_iter(text).map(whole).collect(),
+            ExtractPattern::Oaid => {
+                let mut found: Vec<Match> = uuid_regex().find_iter(text).map(whole).collect();
+                found.extend(
+                    alnum_run()
+                        .find_iter(text)
+                        .filter(|m| {
+                            matches!(m.len(), 16 | 64)
+                                && m.as_str().chars().all(|c| c.is_ascii_hexdigit())
+                                // 纯数字更可能是普通ID
+                                && !m.as_str().chars().all(|c| c.is_ascii_digit())
+                        })
+                        // UUID中不会出现16/64位连续十六进制，不会重复
+                        .map(whole),
+                );
+                found
+            }
+            ExtractPattern::Custom { pattern } => {
+                let regex = Regex::new(pattern)
+                    .map_err(|e| AppError::InvalidInput(format!("正则表达式无效: {}", e)))?;
+                regex
+                    .captures_iter(text)
+                    .filter_map(|caps| caps.get(1).or_else(|| caps.get(0)))
+                    .filter(|m| !m.as_str().is_empty())
+                    .map(whole)
+                    .collect()
+            }
+        };
+        Ok(matches)
+    }
+}
+
+// 提取选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractOptions {
+    pub patterns: Vec<ExtractPattern>,
+    pub dedupe: bool,
+}
+
+// 按所有规则查找匹配，按在原文中出现的顺序排列
+pub fn find_all(text: &str, patterns: &[ExtractPattern]) -> AppResult<Vec<Match>> {
+    if patterns.is_empty() {
+        return Err(AppError::InvalidInput("至少需要选择一种提取规则".to_string()));
+    }
+    let mut matches = Vec::new();
+    for pattern in patterns {
+        matches.extend(pattern.find(text)?);
+    }
+    matches.sort_by_key(|m| (m.start, m.end));
+    Ok(matches)
+}
+
+// 从任意文本中提取ID，每行一个，可直接用于其他操作
+pub fn extract_ids(input: &str, options: &ExtractOptions) -> AppResult<String> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+    let mut seen = HashSet::new();
+    let values: Vec<String> = find_all(input, &options.patterns)?
+        .into_iter()
+        .map(|m| m.value)
+        .filter(|v| !options.dedupe || seen.insert(v.clone()))
+        .collect();
+    Ok(values.join("\n"))
+}
+
+// 原位替换的方向
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InPlaceMode {
+    // 把文本中的数字ID替换为加密串
+    Encrypt,
+    // 把文本中当前配置的加密串替换为原始ID
+    Decrypt,
+}
+
+// 原位替换选项；min_length/max_length只在加密时用于识别数字ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InPlaceOptions {
+    pub mode: InPlaceMode,
+    pub min_length: usize,
+    pub max_length: usize,
+}
+
+// 替换失败的片段
+#[derive(Debug, Clone, Serialize)]
+pub struct InPlaceFailure {
+    pub line: usize,
+    pub value: String,
+    pub error: String,
+}
+
+// 原位替换结果
+#[derive(Debug, Clone, Serialize)]
+pub struct InPlaceReport {
+    pub output: String,
+    pub replaced: usize,
+    pub failures: Vec<InPlaceFailure>,
+}
+
+// 保持其余文本不变，只把匹配到的ID替换为加密/解密结果
+pub fn replace_in_place(input: &str, options: &InPlaceOptions) -> AppResult<InPlaceReport> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+
+    // 解密时，带加密前缀但解不开的片段也作为候选，以便报告失败
+    let candidates: Vec<Match> = match options.mode {
+        InPlaceMode::Encrypt => ExtractPattern::DigitIds {
+            min_length: options.min_length,
+            max_length: options.max_length,
+        }
+        .find(input)?,
+        InPlaceMode::Decrypt => alnum_run()
+            .find_iter(input)
+            .filter(|m| is_profile_hash(m.as_str()) || has_profile_prefix(m.as_str()))
+            .map(|m| Match {
+                start: m.start(),
+                end: m.end(),
+                value: m.as_str().to_string(),
+            })
+            .collect(),
+    };
+
+    let mut output = String::with_capacity(input.len());
+    let mut replaced = 0;
+    let mut failures = Vec::new();
+    let mut last = 0;
+    for m in candidates {
+        let result = match options.mode {
+            InPlaceMode::Encrypt => encrypt_number(&m.value),
+            InPlaceMode::Decrypt => decrypt_text(&m.value),
+        };
+        output.push_str(&input[last..m.start]);
+        match result {
+            Ok(value) => {
+                output.push_str(&value);
+                replaced += 1;
+            }
+            Err(e) => {
+                output.push_str(&m.value);
+                failures.push(InPlaceFailure {
+                    line: input[..m.start].matches('\n').count() + 1,
+                    value: m.value,
+                    error: e.to_string(),
+                });
+            }
+        }
+        last = m.end;
+    }
+    output.push_str(&input[last..]);
+
+    Ok(InPlaceReport {
+        output,
+        replaced,
+        failures,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::lock_config_for_test;
+
+    fn options(patterns: Vec<ExtractPattern>) -> ExtractOptions {
+        ExtractOptions {
+            patterns,
+            dedupe: true,
+        }
+    }
+
+    #[test]
+    fn test_extract_digit_ids_and_mobiles() {
+        let text = r#"{"uid": 10086, "phone": "+8613800138000", "order": 123456789012}, uid=10086"#;
+        let ids = extract_ids(
+            text,
+            &options(vec![ExtractPattern::DigitIds { min_length: 5, max_length: 5 }]),
+        )
+        .unwrap();
+        assert_eq!(ids, "10086");
+
+        let phones = extract_ids(text, &options(vec![ExtractPattern::Mobile])).unwrap();
+        assert_eq!(phones, "13800138000");
+    }
+
+    #[test]
+    fn test_digits_inside_tokens_are_ignored() {
+        let text = "order_A123 v2 sha=9f86d081884c7d65 id:10086 用户10010";
+        let ids = extract_ids(
+            text,
+            &options(vec![ExtractPattern::DigitIds { min_length: 1, max_length: 20 }]),
+        )
+        .unwrap();
+        assert_eq!(ids, "10086\n10010");
+
+        let encrypt = InPlaceOptions {
+            mode: InPlaceMode::Encrypt,
+            min_length: 3,
+            max_length: 20,
+        };
+        let report = replace_in_place("order_A123 v2", &encrypt).unwrap();
+        assert_eq!(report.output, "order_A123 v2");
+        assert_eq!(report.replaced, 0);
+    }
+
+    #[test]
+    fn test_extract_uuid_oaid_and_custom() {
+        let text = "设备A1B2C3D4-0000-1111-2222-333344445555，oaid=0123456789abcdef，订单号:NO.778899";
+        let uuids = extract_ids(text, &options(vec![ExtractPattern::Uuid])).unwrap();
+        assert_eq!(uuids, "A1B2C3D4-0000-1111-2222-333344445555");
+
+        let oaids = extract_ids(text, &options(vec![ExtractPattern::Oaid])).unwrap();
+        assert_eq!(oaids, "A1B2C3D4-0000-1111-2222-333344445555\n0123456789abcdef");
+
+        let custom = extract_ids(
+            text,
+            &options(vec![ExtractPattern::Custom { pattern: r"NO\.(\d+)".to_string() }]),
+        )
+        .unwrap();
+        assert_eq!(custom, "778899");
+
+        let invalid = ExtractPattern::Custom { pattern: "(".to_string() };
+        assert!(extract_ids(text, &options(vec![invalid])).is_err());
+    }
+
+    #[test]
+    fn test_extract_hashes() {
+        let _guard = lock_config_for_test();
+        let hash = encrypt_number("12345").unwrap();
+        let text = format!("log: user {} clicked, ref=abc", hash);
+        let found = extract_ids(&text, &options(vec![ExtractPattern::Hash])).unwrap();
+        assert_eq!(found, hash);
+    }
+
+    #[test]
+    fn test_replace_in_place_round_trip() {
+        let _guard = lock_config_for_test();
+        let text = "UPDATE t SET flag=1 WHERE uid IN (10086, 10010); -- 2024";
+        let encrypt = InPlaceOptions {
+            mode: InPlaceMode::Encrypt,
+            min_length: 5,
+            max_length: 20,
+        };
+        let encrypted = replace_in_place(text, &encrypt).unwrap();
+        assert_eq!(encrypted.replaced, 2);
+        assert!(encrypted.output.starts_with("UPDATE t SET flag=1 WHERE uid IN ("));
+        assert!(encrypted.output.ends_with("); -- 2024"));
+
+        let decrypt = InPlaceOptions {
+            mode: InPlaceMode::Decrypt,
+            ..encrypt
+        };
+        let decrypted = replace_in_place(&encrypted.output, &decrypt).unwrap();
+        assert_eq!(decrypted.output, text);
+        assert_eq!(decrypted.replaced, 2);
+    }
+}
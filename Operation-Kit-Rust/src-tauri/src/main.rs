@@ -7,6 +7,31 @@ mod crypto;       // 加密解密相关功能
 mod error;        // 错误处理模块
 mod text_processor;   // 文本处理相关功能
 mod oss;          // OSS上传功能
+mod s3;           // S3兼容存储
+mod storage;      // 存储后端抽象和本地文件夹
+mod target;       // 上传目标配置
+mod store;        // 按名称保存的JSON配置（配方、上传目标）
+mod vault;        // 加密保存的访问凭证
+mod sql;          // SQL生成功能
+mod recipe;       // 配方（多步骤处理流程）
+mod table;        // CSV/TSV表格解析
+mod xlsx;         // Excel导入导出
+mod precision;    // 科学计数法和精度丢失检查
+mod encoding;     // 文件编码识别与转换
+mod sanitize;     // 不可见字符清理
+mod extract;      // 从文本中提取ID
+mod validate;     // ID格式校验
+mod phone;        // 手机号规范化
+mod mask;         // 敏感信息脱敏
+mod stats;        // 列表统计和数据质量检查
+mod split;        // 大列表拆分为多个文件
+mod template;     // 按模板逐条渲染
+mod codec;        // base64/URL/Unicode/十六进制编解码
+mod timestamp;    // 时间戳转换
+mod snowflake;    // 雪花ID解析
+mod radix;        // 进制转换
+#[cfg(test)]
+mod test_support; // 测试用的临时目录等辅助工具
 
 // 导入commands模块中的所有公开项
 use commands::*;
@@ -22,15 +47,53 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             process_batch_encrypt,    // 批量加密处理
             process_batch_decrypt,    // 批量解密处理
+            process_decode_snowflake, // 解析雪花ID
+            process_convert_radix,    // 进制转换
+            process_sanitize,         // 清理不可见字符
+            process_extract_ids,      // 从文本中提取ID
+            process_replace_in_place, // 原位加密/解密文本中的ID
+            process_validate_ids,     // 按规则校验ID
+            process_normalize_phones, // 规范化手机号
+            process_mask,             // 敏感信息脱敏
+            process_list_stats,       // 列表统计
+            process_check_precision,  // 检查科学计数法和精度丢失
             process_convert_format,   // 格式转换处理
             process_replace_commas,   // 替换逗号处理
             process_add_quotes,       // 添加引号处理
             process_remove_quotes,    // 移除引号处理
+            process_generate_sql,     // 生成SQL
+            process_render_template,  // 按模板逐条渲染
+            process_codec,            // base64/URL/Unicode/十六进制编解码
+            process_timestamps,       // 时间戳与格式化时间互转
+            process_extract_column,   // 提取表格列
+            process_transform_column, // 处理表格列并写回
+            import_text_file,         // 导入文本文件
+            export_text_file,         // 导出文本文件
+            export_split_files,       // 拆分为多个文件导出
+            list_xlsx_sheets,         // 列出Excel工作表
+            import_xlsx_column,       // 从Excel导入列
+            export_xlsx,              // 导出为Excel
+            list_recipes,             // 获取已保存的配方
+            save_recipe_config,       // 保存配方
+            delete_recipe_config,     // 删除配方
+            process_run_recipe,       // 执行配方
             get_crypto_config,        // 获取当前加密配置
             set_crypto_config,        // 设置加密配置
             get_huawei_prefix_config, // 获取华为前缀设置
             set_huawei_prefix_config, // 设置华为前缀设置
-            upload_to_oss,            // 上传到OSS
+            list_oss_targets,         // 获取上传目标
+            save_oss_target,          // 保存上传目标
+            delete_oss_target,        // 删除上传目标
+            get_vault_status,         // 获取凭证保险库状态
+            unlock_credential_vault,  // 用主密码解锁保险库
+            lock_credential_vault,    // 锁定保险库
+            list_saved_credentials,   // 获取已保存的凭证
+            save_credential_config,   // 保存凭证
+            delete_credential_config, // 删除凭证
+            upload_to_storage,        // 上传到目标存储
+            list_storage_objects,     // 列出目标中的对象
+            read_storage_object,      // 读取目标中的对象
+            delete_storage_object,    // 删除目标中的对象
         ])
         // 运行Tauri应用，使用默认上下文配置
         .run(tauri::generate_context!())
@@ -0,0 +1,149 @@
+use crate::error::{AppError, AppResult};
+use crate::text_processor::{split_items, uses_newlines};
+use crate::validate::Validator;
+use serde::{Deserialize, Serialize};
+
+// 脱敏规则
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaskRule {
+    // 按身份证、手机号、邮箱、姓名自动识别，识别不了的按通用规则处理
+    #[default]
+    Auto,
+    // 保留前3后4：138****8000
+    Phone,
+    // 保留前6后4：110101********1234
+    IdCard,
+    // 保留用户名首字符和域名：a***@example.com
+    Email,
+    // 保留姓和末字：张*三，两个字时只保留姓
+    Name,
+    // 保留前keep_first位和后keep_last位
+    Generic,
+}
+
+// 脱敏选项；keep_first/keep_last用于通用规则，也是自动识别失败时的规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MaskOptions {
+    pub rule: MaskRule,
+    pub mask_char: char,
+    pub keep_first: usize,
+    pub keep_last: usize,
+}
+
+impl Default for MaskOptions {
+    fn default() -> Self {
+        Self {
+            rule: MaskRule::Auto,
+            mask_char: '*',
+            keep_first: 3,
+            keep_last: 4,
+        }
+    }
+}
+
+// 保留前后若干个字符，中间替换为掩码；太短时全部替换
+fn keep_ends(value: &str, first: usize, last: usize, mask_char: char) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= first + last {
+        return mask_char.to_string().repeat(chars.len());
+    }
+    let mut masked: String = chars[..first].iter().collect();
+    masked.push_str(&mask_char.to_string().repeat(chars.len() - first - last));
+    masked.extend(&chars[chars.len() - last..]);
+    masked
+}
+
+fn is_chinese_name(value: &str) -> bool {
+    let count = value.chars().count();
+    (2..=6).contains(&count)
+        && value.chars().all(|c| matches!(c, '\u{4E00}'..='\u{9FFF}' | '·'))
+}
+
+fn is_email(value: &str) -> bool {
+    matches!(value.split_once('@'), Some((user, domain)) if !user.is_empty() && domain.contains('.'))
+}
+
+// 自动识别类型，复用ID校验规则
+fn detect_rule(value: &str) -> MaskRule {
+    if Validator::IdCard.check(value).is_ok() {
+        MaskRule::IdCard
+    } else if Validator::Mobile.check(value).is_ok() {
+        MaskRule::Phone
+    } else if is_email(value) {
+        MaskRule::Email
+    } else if is_chinese_name(value) {
+        MaskRule::Name
+    } else {
+        MaskRule::Generic
+    }
+}
+
+// 对单个值脱敏
+pub fn mask_value(value: &str, options: &MaskOptions) -> String {
+    let rule = match options.rule {
+        MaskRule::Auto => detect_rule(value),
+        rule => rule,
+    };
+    let mask_char = options.mask_char;
+    match rule {
+        MaskRule::Phone => keep_ends(value, 3, 4, mask_char),
+        MaskRule::IdCard => keep_ends(value, 6, 4, mask_char),
+        MaskRule::Email => match value.split_once('@') {
+            Some((user, domain)) => format!("{}@{}", keep_ends(user, 1, 0, mask_char), domain),
+            None => keep_ends(value, options.keep_first, options.keep_last, mask_char),
+        },
+        MaskRule::Name => match value.chars().count() {
+            0..=1 => keep_ends(value, 0, 0, mask_char),
+            2 => keep_ends(value, 1, 0, mask_char),
+            _ => keep_ends(value, 1, 1, mask_char),
+        },
+        MaskRule::Auto | MaskRule::Generic => {
+            keep_ends(value, options.keep_first, options.keep_last, mask_char)
+        }
+    }
+}
+
+// 对列表中的每一项脱敏，保留原分隔符
+pub fn mask_list(input: &str, options: &MaskOptions) -> AppResult<String> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+
+    let items: Vec<String> = split_items(input)
+        .into_iter()
+        .map(|item| mask_value(item, options))
+        .collect();
+    let delimiter = if uses_newlines(input) { "\n" } else { "," };
+    Ok(items.join(delimiter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_mask_mixed_list() {
+        let input = "13800138000\n11010519491231002X\nalice@example.com\n张小三\n李四\n1234567890";
+        let masked = mask_list(input, &MaskOptions::default()).unwrap();
+        assert_eq!(
+            masked,
+            "138****8000\n110105********002X\na****@example.com\n张*三\n李*\n123***7890"
+        );
+    }
+
+    #[test]
+    fn test_explicit_rule_and_mask_char() {
+        let options = MaskOptions {
+            rule: MaskRule::Generic,
+            mask_char: '#',
+            keep_first: 1,
+            keep_last: 1,
+        };
+        assert_eq!(mask_list("abcd,xy", &options).unwrap(), "a##d,##");
+
+        let options = MaskOptions { rule: MaskRule::Phone, ..MaskOptions::default() };
+        assert_eq!(mask_value("+8613800138000", &options), "+86*******8000");
+    }
+}
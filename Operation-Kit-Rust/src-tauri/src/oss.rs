@@ -1,9 +1,14 @@
 use anyhow::{anyhow, Result};
 use hmac::{Hmac, Mac};
 use sha1::Sha1;
-use reqwest::header::{HeaderMap, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE, DATE, HOST};
-use chrono::Utc;
+use sha2::{Digest, Sha256};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE, DATE, HOST, LAST_MODIFIED};
+use reqwest::{Method, Response, StatusCode};
+use chrono::{DateTime, Utc};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use crate::storage::{content_type, normalize_last_modified, parse_list_xml, ObjectInfo, StorageBackend};
+use crate::target::{OssTarget, SignatureVersion};
+use crate::vault::CredentialSecret;
 
 // 定义支持的渠道
 pub enum Channel {
@@ -38,6 +43,17 @@ impl Channel {
 
 // 用于生成OSS签名的类型
 type HmacSha1 = Hmac<Sha1>;
+type HmacSha256 = Hmac<Sha256>;
+
+// V4签名算法名称
+const V4_ALGORITHM: &str = "OSS4-HMAC-SHA256";
+// V4签名不对请求体计算哈希
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+// Date头使用的HTTP日期格式
+fn http_date(now: DateTime<Utc>) -> String {
+    now.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
 
 // 计算MD5并转为Base64格式
 fn md5_base64(data: &[u8]) -> String {
@@ -45,110 +61,361 @@ fn md5_base64(data: &[u8]) -> String {
     BASE64.encode(digest.as_ref())
 }
 
-// 上传ID列表到OSS
-pub async fn upload_ids_to_oss(
-    access_id: &str, 
-    access_key: &str, 
-    content: &str, 
-    channel: Channel
-) -> Result<String> {
-    // 检查OSS参数
-    if access_id.trim().is_empty() || access_key.trim().is_empty() {
-        return Err(anyhow!("OSS Access ID或Access Key不能为空"));
-    }
-    
-    // 验证输入内容是否符合要求 (每行一个数字ID)
-    validate_content(content)?;
+// 构建规范OSS头(CanonicalizedOSSHeaders)
+// 取所有以x-oss-开头的头，名称转小写、去掉值两端空白后按名称排序，每个头以 "name:value\n" 拼接
+fn canonicalized_oss_headers(headers: &[(String, String)]) -> String {
+    let mut oss_headers: Vec<(String, &str)> = headers
+        .iter()
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim()))
+        .filter(|(name, _)| name.starts_with("x-oss-"))
+        .collect();
+    oss_headers.sort();
+    oss_headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect()
+}
 
-    // 桶名称和对象路径
-    let bucket_name = "Bucket";
-    let endpoint = "endpoint";
-    let host = format!("{}.{}", bucket_name, endpoint);
-    
-    // 设置文件夹路径和文件名
-    let folder_name = format!("hive2/dim/tmp_{}_ids", channel.as_str());
-    let file_name = format!("{}.txt", channel.as_str());  // 使用渠道名作为文件名，添加.txt后缀
-    let object_path = format!("{}/{}", folder_name, file_name);  // 完整对象路径
-    
-    // 转换内容为字节
-    let content_bytes = content.as_bytes();
-    let content_length = content_bytes.len();
-    
-    // 构建请求头和签名相关信息
-    let date = Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
-    let content_type = "text/plain";
-    
-    // 计算Content-MD5 - OSS API要求对内容计算MD5并Base64编码
-    let content_md5 = md5_base64(content_bytes);
-    
-    // 构建规范资源(CanonicalizedResource)
-    let canonicalized_resource = format!("/{}/{}", bucket_name, object_path);
-    
-    // 构建用于签名的规范字符串
-    // 格式: VERB + "\n" + Content-MD5 + "\n" + Content-Type + "\n" + Date + "\n" + CanonicalizedOSSHeaders + CanonicalizedResource
-    let string_to_sign = format!(
-        "PUT\n{}\n{}\n{}\n{}",
+// 构建用于签名的规范字符串
+// 格式: VERB + "\n" + Content-MD5 + "\n" + Content-Type + "\n" + Date + "\n" + CanonicalizedOSSHeaders + CanonicalizedResource
+fn string_to_sign_v1(
+    verb: &str,
+    content_md5: &str,
+    content_type: &str,
+    date: &str,
+    headers: &[(String, String)],
+    canonicalized_resource: &str,
+) -> String {
+    format!(
+        "{}\n{}\n{}\n{}\n{}{}",
+        verb,
         content_md5,
         content_type,
         date,
+        canonicalized_oss_headers(headers),
         canonicalized_resource
-    );
-    
-    // 使用HMAC-SHA1计算签名
+    )
+}
+
+// 使用HMAC-SHA1计算签名
+fn sign_v1(access_key: &str, string_to_sign: &str) -> Result<String> {
     let mut mac = HmacSha1::new_from_slice(access_key.as_bytes())
         .map_err(|e| anyhow!("初始化HMAC失败: {}", e))?;
     mac.update(string_to_sign.as_bytes());
-    let result = mac.finalize();
-    let signature = BASE64.encode(result.into_bytes());
-    
-    // 构建Authorization头
-    let authorization = format!("OSS {}:{}", access_id, signature);
-    
-    // 构建完整URL
-    let url = format!("https://{}/{}", host, object_path);
-    
-    // 创建请求头
-    let mut headers = HeaderMap::new();
-    headers.insert(HOST, HeaderValue::from_str(&host)?);
-    headers.insert(DATE, HeaderValue::from_str(&date)?);
-    headers.insert(CONTENT_TYPE, HeaderValue::from_str(content_type)?);
-    headers.insert(CONTENT_LENGTH, HeaderValue::from_str(&content_length.to_string())?);
-    headers.insert("Content-MD5", HeaderValue::from_str(&content_md5)?);
-    headers.insert("Authorization", HeaderValue::from_str(&authorization)?);
-    
-    // 发送PUT请求
-    let client = reqwest::Client::new();
-    let response = client
-        .put(&url)
-        .headers(headers)
-        .body(content.to_string())
-        .send()
-        .await
-        .map_err(|e| anyhow!("发送请求失败: {}", e))?;
-    
+    Ok(BASE64.encode(mac.finalize().into_bytes()))
+}
+
+pub fn hmac_sha256(key: &[u8], data: &str) -> Result<Vec<u8>> {
+    let mut mac = HmacSha256::new_from_slice(key)
+        .map_err(|e| anyhow!("初始化HMAC失败: {}", e))?;
+    mac.update(data.as_bytes());
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+// URI编码：除 A-Z a-z 0-9 - _ . ~ 以外的字节都编码为%XX；对象路径中的/按需保留
+pub fn uri_encode(value: &str, encode_slash: bool) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            b'/' if !encode_slash => "/".to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+// 规范查询字符串：参数名和值分别编码后按名称排序，以&连接
+pub fn canonical_query(query: &[(&str, String)]) -> String {
+    let mut params: Vec<(String, String)> = query
+        .iter()
+        .map(|(name, value)| (uri_encode(name, true), uri_encode(value, true)))
+        .collect();
+    params.sort();
+    params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+// 构建V4规范请求(CanonicalRequest)
+// 格式: VERB + "\n" + CanonicalURI + "\n" + CanonicalQueryString + "\n" + CanonicalHeaders + "\n" + AdditionalHeaders + "\n" + HashedPayload
+// CanonicalHeaders包含Content-Type、Content-MD5和所有x-oss-*头，名称小写并排序
+fn canonical_request_v4(
+    verb: &str,
+    bucket: &str,
+    object_path: &str,
+    query: &[(&str, String)],
+    headers: &[(String, String)],
+) -> String {
+    let mut signed_headers: Vec<(String, &str)> = headers
+        .iter()
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim()))
+        .filter(|(name, _)| name == "content-type" || name == "content-md5" || name.starts_with("x-oss-"))
+        .collect();
+    signed_headers.sort();
+    let canonical_headers: String = signed_headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect();
+    format!(
+        "{}\n/{}/{}\n{}\n{}\n\n{}",
+        verb,
+        bucket,
+        uri_encode(object_path, false),
+        canonical_query(query),
+        canonical_headers,
+        UNSIGNED_PAYLOAD
+    )
+}
+
+// 签名范围：日期/地域/oss/aliyun_v4_request
+fn scope_v4(now: DateTime<Utc>, region: &str) -> String {
+    format!("{}/{}/oss/aliyun_v4_request", now.format("%Y%m%d"), region)
+}
+
+// 构建V4待签名字符串
+// 格式: "OSS4-HMAC-SHA256" + "\n" + 时间戳 + "\n" + 签名范围 + "\n" + hex(SHA256(CanonicalRequest))
+fn string_to_sign_v4(now: DateTime<Utc>, region: &str, canonical_request: &str) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        V4_ALGORITHM,
+        now.format("%Y%m%dT%H%M%SZ"),
+        scope_v4(now, region),
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    )
+}
+
+// 使用HMAC-SHA256计算V4签名
+// 签名密钥由 "aliyun_v4" + AccessKey 依次对日期、地域、"oss"、"aliyun_v4_request" 做HMAC派生
+fn sign_v4(access_key: &str, now: DateTime<Utc>, region: &str, string_to_sign: &str) -> Result<String> {
+    let mut signing_key = hmac_sha256(
+        format!("aliyun_v4{}", access_key).as_bytes(),
+        &now.format("%Y%m%d").to_string(),
+    )?;
+    for part in [region, "oss", "aliyun_v4_request"] {
+        signing_key = hmac_sha256(&signing_key, part)?;
+    }
+    Ok(hex::encode(hmac_sha256(&signing_key, string_to_sign)?))
+}
+
+// 把OSS返回的错误码转为易懂的提示
+async fn oss_error(response: Response) -> anyhow::Error {
     let status = response.status();
+    let error_text = response.text().await
+        .unwrap_or_else(|_| "无法获取错误详情".to_string());
     
-    // 处理响应
-    if status.is_success() {
-        Ok(format!("成功上传到 OSS 路径: {}/{}", host, object_path))
+    if error_text.contains("InvalidAccessKeyId") {
+        anyhow!("Access ID无效，请检查配置")
+    } else if error_text.contains("InvalidSecurityToken") || error_text.contains("SecurityTokenExpired") {
+        anyhow!("STS Security Token无效或已过期，请更新凭证")
+    } else if error_text.contains("SignatureDoesNotMatch") {
+        anyhow!("Access Key无效，请检查配置或签名错误")
+    } else if error_text.contains("NetworkingError") || error_text.contains("Connection refused") {
+        anyhow!("网络连接错误，请检查网络并确认使用了正确的OSS接入点")
     } else {
-        let error_text = response.text().await
-            .unwrap_or_else(|_| "无法获取错误详情".to_string());
-        
-        if error_text.contains("InvalidAccessKeyId") {
-            Err(anyhow!("Access ID无效，请检查配置"))
-        } else if error_text.contains("SignatureDoesNotMatch") {
-            Err(anyhow!("Access Key无效，请检查配置或签名错误"))
-        } else if error_text.contains("NetworkingError") || error_text.contains("Connection refused") {
-            Err(anyhow!("网络连接错误，请检查网络并确认使用了正确的OSS接入点"))
+        anyhow!("OSS请求失败: HTTP {}, 错误: {}", status, error_text)
+    }
+}
+
+// 阿里云OSS存储后端，按目标配置使用V1或V4签名
+pub struct OssBackend {
+    target: OssTarget,
+    credential: CredentialSecret,
+}
+
+impl OssBackend {
+    pub fn new(target: OssTarget, credential: CredentialSecret) -> Self {
+        Self { target, credential }
+    }
+
+    // 计算Authorization头，返回它和需要随请求发送的x-oss-*头；时间由调用方传入，便于用固定时间测试
+    fn authorize(
+        &self,
+        method: &Method,
+        key: &str,
+        query: &[(&str, String)],
+        content_type: &str,
+        content_md5: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(String, Vec<(String, String)>)> {
+        let access_id = self.credential.access_id();
+        let access_key = self.credential.access_key();
+        let bucket_name = self.target.bucket.as_str();
+        let verb = method.as_str();
+
+        // 需要参与签名的x-oss-*头，STS凭证要带上安全令牌
+        let mut oss_headers = Vec::new();
+        if let Some(token) = self.credential.security_token() {
+            oss_headers.push(("x-oss-security-token".to_string(), token.to_string()));
+        }
+
+        // 按目标配置的签名版本构建Authorization头
+        let authorization = match self.target.signature_version {
+            SignatureVersion::V1 => {
+                // 构建规范资源(CanonicalizedResource)，列举对象时的prefix等参数不参与签名
+                let canonicalized_resource = format!("/{}/{}", bucket_name, key);
+                let string_to_sign = string_to_sign_v1(
+                    verb,
+                    content_md5,
+                    content_type,
+                    &http_date(now),
+                    &oss_headers,
+                    &canonicalized_resource,
+                );
+                format!("OSS {}:{}", access_id, sign_v1(access_key, &string_to_sign)?)
+            }
+            SignatureVersion::V4 => {
+                let region = self.target.region.trim();
+                oss_headers.push(("x-oss-date".to_string(), now.format("%Y%m%dT%H%M%SZ").to_string()));
+                oss_headers.push(("x-oss-content-sha256".to_string(), UNSIGNED_PAYLOAD.to_string()));
+                let mut signed_headers = oss_headers.clone();
+                if *method == Method::PUT {
+                    signed_headers.push(("content-type".to_string(), content_type.to_string()));
+                    signed_headers.push(("content-md5".to_string(), content_md5.to_string()));
+                }
+                let canonical_request = canonical_request_v4(verb, bucket_name, key, query, &signed_headers);
+                let string_to_sign = string_to_sign_v4(now, region, &canonical_request);
+                format!(
+                    "{} Credential={}/{},Signature={}",
+                    V4_ALGORITHM,
+                    access_id,
+                    scope_v4(now, region),
+                    sign_v4(access_key, now, region, &string_to_sign)?
+                )
+            }
+        };
+        Ok((authorization, oss_headers))
+    }
+
+    // 签名并发送请求；key为空时访问Bucket本身（用于列举对象）
+    async fn send(&self, method: Method, key: &str, query: &[(&str, String)], body: Vec<u8>) -> Result<Response> {
+        let access_id = self.credential.access_id();
+        let access_key = self.credential.access_key();
+
+        // 检查OSS参数
+        if access_id.trim().is_empty() || access_key.trim().is_empty() {
+            return Err(anyhow!("OSS Access ID或Access Key不能为空"));
+        }
+
+        let host = self.target.host();
+
+        // 构建请求头和签名相关信息
+        let now = Utc::now();
+        let date = http_date(now);
+
+        // 只有PUT带请求体，需要Content-Type和Content-MD5
+        // 计算Content-MD5 - OSS API要求对内容计算MD5并Base64编码
+        let (content_type, content_md5) = if method == Method::PUT {
+            (content_type(key), md5_base64(&body))
         } else {
-            Err(anyhow!("上传到OSS失败: HTTP {}, 错误: {}", status, error_text))
+            ("", String::new())
+        };
+        let (authorization, oss_headers) = self.authorize(&method, key, query, content_type, &content_md5, now)?;
+
+        // 构建完整URL
+        let mut url = format!("https://{}/{}", host, uri_encode(key, false));
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&canonical_query(query));
+        }
+
+        // 创建请求头
+        let mut headers = HeaderMap::new();
+        headers.insert(HOST, HeaderValue::from_str(&host)?);
+        headers.insert(DATE, HeaderValue::from_str(&date)?);
+        if method == Method::PUT {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_str(content_type)?);
+            headers.insert(CONTENT_LENGTH, HeaderValue::from_str(&body.len().to_string())?);
+            headers.insert("Content-MD5", HeaderValue::from_str(&content_md5)?);
+        }
+        headers.insert("Authorization", HeaderValue::from_str(&authorization)?);
+        for (name, value) in &oss_headers {
+            headers.insert(HeaderName::from_bytes(name.as_bytes())?, HeaderValue::from_str(value)?);
+        }
+
+        reqwest::Client::new()
+            .request(method, &url)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| anyhow!("发送请求失败: {}", e))
+    }
+}
+
+impl StorageBackend for OssBackend {
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        let response = self.send(Method::PUT, key, &[], body).await?;
+        if !response.status().is_success() {
+            return Err(oss_error(response).await);
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let response = self.send(Method::GET, key, &[], Vec::new()).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(anyhow!("对象不存在: {}", key));
+        }
+        if !response.status().is_success() {
+            return Err(oss_error(response).await);
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    // 每页最多1000个对象，以上一页最后一个key作为marker翻页
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        let mut objects = Vec::new();
+        loop {
+            let mut query = vec![("prefix", prefix.to_string()), ("max-keys", "1000".to_string())];
+            if let Some(last) = objects.last().map(|o: &ObjectInfo| o.key.clone()) {
+                query.push(("marker", last));
+            }
+            let response = self.send(Method::GET, "", &query, Vec::new()).await?;
+            if !response.status().is_success() {
+                return Err(oss_error(response).await);
+            }
+            let (page, truncated) = parse_list_xml(&response.text().await?)?;
+            let empty = page.is_empty();
+            objects.extend(page);
+            if !truncated || empty {
+                return Ok(objects);
+            }
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let response = self.send(Method::DELETE, key, &[], Vec::new()).await?;
+        if !response.status().is_success() {
+            return Err(oss_error(response).await);
+        }
+        Ok(())
+    }
+
+    async fn head(&self, key: &str) -> Result<Option<ObjectInfo>> {
+        let response = self.send(Method::HEAD, key, &[], Vec::new()).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
         }
+        if !response.status().is_success() {
+            return Err(oss_error(response).await);
+        }
+        let header = |name| response.headers().get(name).and_then(|v| v.to_str().ok());
+        Ok(Some(ObjectInfo {
+            key: key.to_string(),
+            size: header(CONTENT_LENGTH).and_then(|v| v.parse().ok()).unwrap_or(0),
+            last_modified: header(LAST_MODIFIED).map(normalize_last_modified),
+        }))
+    }
+
+    fn location(&self, key: &str) -> String {
+        format!("OSS 路径: {}/{}", self.target.host(), key)
     }
 }
 
 // 验证内容是否满足格式要求 (每行一个数字ID)
-fn validate_content(content: &str) -> Result<()> {
+pub fn validate_content(content: &str) -> Result<()> {
     if content.trim().is_empty() {
         return Err(anyhow!("内容不能为空"));
     }
@@ -170,6 +437,21 @@ fn validate_content(content: &str) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::target::BackendKind;
+
+    fn backend(signature_version: SignatureVersion, credential: CredentialSecret) -> OssBackend {
+        let target = OssTarget {
+            name: "测试".to_string(),
+            backend: BackendKind::Oss,
+            bucket: "ids".to_string(),
+            endpoint: "oss-cn-hangzhou.aliyuncs.com".to_string(),
+            key_template: "hive2/tmp_{channel}_ids/{channel}.txt".to_string(),
+            signature_version,
+            region: "cn-hangzhou".to_string(),
+            path: String::new(),
+        };
+        OssBackend::new(target, credential)
+    }
 
     #[test]
     fn test_validate_content_valid() {
@@ -192,4 +474,119 @@ mod tests {
         assert_eq!(Channel::Vivo.as_str(), "vivo");
         assert_eq!(Channel::Oppo.as_str(), "oppo");
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_canonicalized_oss_headers() {
+        let headers = vec![
+            ("X-OSS-Meta-Author".to_string(), " foo@example.com ".to_string()),
+            ("Content-Type".to_string(), "text/html".to_string()),
+            ("x-oss-magic".to_string(), "abracadabra".to_string()),
+        ];
+        assert_eq!(
+            canonicalized_oss_headers(&headers),
+            "x-oss-magic:abracadabra\nx-oss-meta-author:foo@example.com\n"
+        );
+        assert_eq!(canonicalized_oss_headers(&[]), "");
+    }
+
+    #[test]
+    fn test_sign_v1_with_oss_headers() {
+        // 阿里云OSS文档《在Header中包含签名》中 PUT /nelson 的示例，待签名字符串与文档一致
+        // 文档给出的签名值与示例密钥算不出来，签名一项是按该示例自行生成的回归值
+        let headers = vec![
+            ("x-oss-meta-author".to_string(), "foo@example.com".to_string()),
+            ("x-oss-magic".to_string(), "abracadabra".to_string()),
+        ];
+        let string_to_sign = string_to_sign_v1(
+            "PUT",
+            "ODBGOERFMDMzQTczRUY3NUE3NzA5QzdFNUYzMDQxNEM=",
+            "text/html",
+            "Thu, 17 Nov 2005 18:49:58 GMT",
+            &headers,
+            "/oss-example/nelson",
+        );
+        assert_eq!(
+            string_to_sign,
+            "PUT\nODBGOERFMDMzQTczRUY3NUE3NzA5QzdFNUYzMDQxNEM=\ntext/html\nThu, 17 Nov 2005 18:49:58 GMT\n\
+             x-oss-magic:abracadabra\nx-oss-meta-author:foo@example.com\n/oss-example/nelson"
+        );
+        let signature = sign_v1("OtxrzxIsfpFjA7SwPzILwy8Bw21TLhquhboDYROV", &string_to_sign).unwrap();
+        assert_eq!(signature, "fV5fq7DPwNbrrig7nvUSZIVyruI=");
+    }
+
+    #[test]
+    fn test_sign_v4_with_pinned_clock() {
+        // 固定时间和请求参数，期望值是自行生成的回归值，用于发现签名步骤的意外改动
+        let now = DateTime::parse_from_rfc3339("2024-03-15T08:30:00Z").unwrap().with_timezone(&Utc);
+        let headers = vec![
+            ("Content-Type".to_string(), "text/plain".to_string()),
+            ("Content-MD5".to_string(), "eB5eJF1ptWaXm4bijSPyxw==".to_string()),
+            ("x-oss-date".to_string(), "20240315T083000Z".to_string()),
+            ("x-oss-content-sha256".to_string(), UNSIGNED_PAYLOAD.to_string()),
+            ("Host".to_string(), "ids.oss-cn-hangzhou.aliyuncs.com".to_string()),
+        ];
+        let canonical_request = canonical_request_v4("PUT", "ids", "hive2/tmp ids/vivo.txt", &[], &headers);
+        assert_eq!(
+            canonical_request,
+            "PUT\n/ids/hive2/tmp%20ids/vivo.txt\n\n\
+             content-md5:eB5eJF1ptWaXm4bijSPyxw==\ncontent-type:text/plain\n\
+             x-oss-content-sha256:UNSIGNED-PAYLOAD\nx-oss-date:20240315T083000Z\n\n\nUNSIGNED-PAYLOAD"
+        );
+
+        let string_to_sign = string_to_sign_v4(now, "cn-hangzhou", &canonical_request);
+        assert_eq!(
+            string_to_sign,
+            "OSS4-HMAC-SHA256\n20240315T083000Z\n20240315/cn-hangzhou/oss/aliyun_v4_request\n\
+             c00d096c555d1857df767400ae6e38eaf979015dbbf186323c9045ac41f87dd8"
+        );
+        let signature = sign_v4("TestSecretKey", now, "cn-hangzhou", &string_to_sign).unwrap();
+        assert_eq!(signature, "a3026b5a5d45339b2a5160ba23745e0731fac00cff8227b0fa321b2c281913d1");
+    }
+
+    #[test]
+    fn test_sign_v4_list_request() {
+        // 列举对象：无请求体，prefix等查询参数参与V4签名；期望值是自行生成的回归值
+        let now = DateTime::parse_from_rfc3339("2024-03-15T08:30:00Z").unwrap().with_timezone(&Utc);
+        let query = [("prefix", "hive2/tmp_vivo_ids/".to_string()), ("max-keys", "1000".to_string())];
+        let headers = vec![
+            ("x-oss-date".to_string(), "20240315T083000Z".to_string()),
+            ("x-oss-content-sha256".to_string(), UNSIGNED_PAYLOAD.to_string()),
+        ];
+        assert_eq!(
+            canonical_request_v4("GET", "ids", "", &query, &headers),
+            "GET\n/ids/\nmax-keys=1000&prefix=hive2%2Ftmp_vivo_ids%2F\n\
+             x-oss-content-sha256:UNSIGNED-PAYLOAD\nx-oss-date:20240315T083000Z\n\n\nUNSIGNED-PAYLOAD"
+        );
+
+        let credential = CredentialSecret::AccessKey {
+            access_id: "LTAI-test".to_string(),
+            access_key: "TestSecretKey".to_string(),
+        };
+        let (authorization, oss_headers) = backend(SignatureVersion::V4, credential)
+            .authorize(&Method::GET, "", &query, "", "", now)
+            .unwrap();
+        assert_eq!(
+            authorization,
+            "OSS4-HMAC-SHA256 Credential=LTAI-test/20240315/cn-hangzhou/oss/aliyun_v4_request,\
+             Signature=150f360a2e2d29b1cd015320126a1322c9f00e1cd2252e3e36afa8614facdd0d"
+        );
+        assert_eq!(oss_headers, headers);
+    }
+
+    #[test]
+    fn test_sign_v1_head_request_with_sts() {
+        // HEAD请求没有Content-MD5和Content-Type，STS令牌作为x-oss-*头参与签名
+        let now = DateTime::parse_from_rfc3339("2024-03-15T08:30:00Z").unwrap().with_timezone(&Utc);
+        let credential = CredentialSecret::Sts {
+            access_id: "STS.test".to_string(),
+            access_key: "TestSecretKey".to_string(),
+            security_token: "token-123".to_string(),
+            expiration: "2024-03-15T09:30:00Z".to_string(),
+        };
+        let (authorization, oss_headers) = backend(SignatureVersion::V1, credential)
+            .authorize(&Method::HEAD, "hive2/tmp_vivo_ids/vivo.txt", &[], "", "", now)
+            .unwrap();
+        assert_eq!(authorization, "OSS STS.test:YoDx8MoRjwaAU5x6rfNTYsf99T8=");
+        assert_eq!(oss_headers, vec![("x-oss-security-token".to_string(), "token-123".to_string())]);
+    }
+}
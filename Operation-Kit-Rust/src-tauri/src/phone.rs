@@ -0,0 +1,149 @@
+use crate::error::{AppError, AppResult};
+use crate::text_processor::{format_line_issues, split_items_with_lines, uses_newlines};
+use crate::validate::mobile_carrier;
+use serde::{Deserialize, Serialize};
+
+// 规范化后的输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhoneFormat {
+    // 11位纯数字，如 13800138000
+    #[default]
+    National,
+    // E.164格式，如 +8613800138000
+    E164,
+}
+
+// 手机号规范化选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PhoneOptions {
+    pub format: PhoneFormat,
+}
+
+// 无法规范化的条目
+#[derive(Debug, Clone, Serialize)]
+pub struct PhoneIssue {
+    pub line: usize,
+    pub value: String,
+    pub message: String,
+}
+
+// 规范化结果；无法规范化的条目原样保留在输出中
+#[derive(Debug, Clone, Serialize)]
+pub struct PhoneReport {
+    pub output: String,
+    pub normalized: usize,
+    pub issues: Vec<PhoneIssue>,
+}
+
+// 把单个号码规范化为11位手机号
+pub fn normalize_phone(value: &str) -> Result<String, String> {
+    let mut digits = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '0'..='9' | '+' => digits.push(c),
+            // 全角数字和加号
+            '０'..='９' | '＋' => digits.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)),
+            ' ' | '-' | '.' | '(' | ')' | '\u{00A0}' | '\u{3000}' | '－' | '（' | '）' => {}
+            _ => return Err(format!("包含无法识别的字符 '{}'", c)),
+        }
+    }
+
+    let number = if let Some(rest) = digits.strip_prefix("+86") {
+        rest
+    } else if let Some(rest) = digits.strip_prefix("0086") {
+        rest
+    } else if digits.len() == 13 && digits.starts_with("86") {
+        &digits[2..]
+    } else if digits.starts_with('+') || digits.starts_with("00") {
+        return Err("不是中国大陆号码".to_string());
+    } else {
+        &digits
+    };
+
+    if mobile_carrier(number).is_none() {
+        return Err("不是有效的大陆手机号".to_string());
+    }
+    Ok(number.to_string())
+}
+
+// 逐行规范化手机号并报告无法处理的条目
+pub fn normalize_phones_report(input: &str, options: &PhoneOptions) -> AppResult<PhoneReport> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+
+    let mut items = Vec::new();
+    let mut issues = Vec::new();
+    for (line, value) in split_items_with_lines(input) {
+        match normalize_phone(value) {
+            Ok(number) => items.push(match options.format {
+                PhoneFormat::National => number,
+                PhoneFormat::E164 => format!("+86{}", number),
+            }),
+            Err(message) => {
+                issues.push(PhoneIssue {
+                    line,
+                    value: value.to_string(),
+                    message,
+                });
+                items.push(value.to_string());
+            }
+        }
+    }
+
+    let delimiter = if uses_newlines(input) { "\n" } else { "," };
+    Ok(PhoneReport {
+        output: items.join(delimiter),
+        normalized: items.len() - issues.len(),
+        issues,
+    })
+}
+
+// 规范化手机号；有任何无法规范化的条目时报错，用于加密等操作之前
+pub fn normalize_phones(input: &str, options: &PhoneOptions) -> AppResult<String> {
+    let report = normalize_phones_report(input, options)?;
+    if report.issues.is_empty() {
+        return Ok(report.output);
+    }
+
+    let issues = report
+        .issues
+        .iter()
+        .map(|issue| (issue.line, issue.value.as_str(), issue.message.as_str()));
+    Err(AppError::InvalidInput(format!("以下号码无法规范化:\n{}", format_line_issues(issues))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_phone() {
+        for raw in [
+            "+86 138-0013-8000",
+            "008613800138000",
+            "(138)00138000",
+            "８６１３８００１３８０００",
+            "138 0013 8000",
+        ] {
+            assert_eq!(normalize_phone(raw), Ok("13800138000".to_string()), "{}", raw);
+        }
+        assert!(normalize_phone("+1 415 555 0100").is_err());
+        assert!(normalize_phone("1380013800").is_err());
+        assert!(normalize_phone("138a0013800").is_err());
+    }
+
+    #[test]
+    fn test_normalize_phones_report() {
+        let options = PhoneOptions { format: PhoneFormat::E164 };
+        let report = normalize_phones_report("+86 138-0013-8000\n12345\n13900139000", &options).unwrap();
+        assert_eq!(report.output, "+8613800138000\n12345\n+8613900139000");
+        assert_eq!(report.normalized, 2);
+        assert_eq!(report.issues[0].line, 2);
+
+        let err = normalize_phones("13800138000\n12345", &PhoneOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("第 2 行"));
+    }
+}
@@ -0,0 +1,236 @@
+use crate::error::{AppError, AppResult};
+use crate::text_processor::{format_line_issues, split_items_with_lines, uses_newlines};
+use serde::Serialize;
+use std::num::IntErrorKind;
+
+// Excel数值只保留15位有效数字
+const EXCEL_PRECISION: usize = 15;
+// 展开后允许的最大位数，超过的不可能是ID，直接拒绝，避免为巨大的指数分配内存
+const MAX_ID_DIGITS: i64 = 64;
+
+// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrecisionIssueKind {
+    // 科学计数法，数字完整，已展开
+    Expanded,
+    // 科学计数法，末尾数字已丢失，无法还原
+    ScientificNotation,
+    // 超过15位且末尾全为0，可能被Excel截断；默认拒绝，确认是真实ID时可以放行
+    PrecisionLoss,
+}
+
+// 单个有问题的条目
+#[derive(Debug, Clone, Serialize)]
+pub struct PrecisionIssue {
+    pub line: usize,
+    pub value: String,
+    pub kind: PrecisionIssueKind,
+    pub message: String,
+}
+
+// 检查结果：展开后的文本和发现的问题；refused统计默认会被拒绝的条目
+#[derive(Debug, Clone, Serialize)]
+pub struct PrecisionReport {
+    pub output: String,
+    pub issues: Vec<PrecisionIssue>,
+    pub refused: usize,
+}
+
+// 单个条目的检查结果
+enum Checked {
+    Ok,
+    Expanded(String),
+    Truncated(String),
+    Refused(String),
+}
+
+// 把科学计数法展开为整数，返回(整数, 是否补了0)；不是科学计数法或不是整数时返回None
+fn expand_scientific(value: &str) -> Option<Result<(String, bool), String>> {
+    let (mantissa, exponent) = value.split_once(['e', 'E'])?;
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let exponent = exponent.strip_prefix('+').unwrap_or(exponent);
+    if int_part.is_empty()
+        || !int_part.chars().all(|c| c.is_ascii_digit())
+        || !frac_part.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let too_long = || Some(Err(format!("科学计数法展开后超过{}位，不是有效的ID", MAX_ID_DIGITS)));
+    let exponent: i64 = match exponent.parse() {
+        Ok(exponent) => exponent,
+        Err(e) if *e.kind() == IntErrorKind::PosOverflow => return too_long(),
+        Err(e) if *e.kind() == IntErrorKind::NegOverflow => return Some(Err("科学计数法表示的不是整数".to_string())),
+        Err(_) => return None,
+    };
+
+    let digits = format!("{}{}", int_part, frac_part);
+    // 小数点在digits中的位置
+    let point = match (int_part.len() as i64).checked_add(exponent) {
+        Some(point) if point <= MAX_ID_DIGITS => point,
+        _ => return too_long(),
+    };
+    if point <= 0 || digits[(point as usize).min(digits.len())..].chars().any(|c| c != '0') {
+        return Some(Err("科学计数法表示的不是整数".to_string()));
+    }
+
+    let point = point as usize;
+    let padded = point > digits.len();
+    let mut integer = if padded {
+        format!("{}{}", digits, "0".repeat(point - digits.len()))
+    } else {
+        digits[..point].to_string()
+    };
+    let trimmed = integer.trim_start_matches('0');
+    integer = if trimmed.is_empty() { "0".to_string() } else { trimmed.to_string() };
+    Some(Ok((integer, padded)))
+}
+
+// 超过15位的纯数字如果第15位之后全是0，很可能是Excel截断了精度
+fn looks_truncated(value: &str) -> bool {
+    value.len() > EXCEL_PRECISION
+        && value.chars().all(|c| c.is_ascii_digit())
+        && value[EXCEL_PRECISION..].chars().all(|c| c == '0')
+}
+
+fn check_item(value: &str) -> Checked {
+    match expand_scientific(value) {
+        Some(Ok((integer, false))) => Checked::Expanded(integer),
+        Some(Ok((integer, true))) => {
+            Checked::Refused(format!("科学计数法丢失了末尾数字，无法还原（近似值 {}）", integer))
+        }
+        Some(Err(message)) => Checked::Refused(message),
+        None if looks_truncated(value) => Checked::Truncated(format!(
+            "超过{}位且末尾全为0，可能被Excel截断了精度，请确认",
+            EXCEL_PRECISION
+        )),
+        None => Checked::Ok,
+    }
+}
+
+// 检查输入中的科学计数法和精度丢失，能还原的展开，不能还原的记录下来
+pub fn check_precision(input: &str) -> AppResult<PrecisionReport> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+
+    let mut items = Vec::new();
+    let mut issues = Vec::new();
+    let mut refused = 0;
+    for (line, value) in split_items_with_lines(input) {
+        match check_item(value) {
+            Checked::Ok => items.push(value.to_string()),
+            Checked::Expanded(integer) => {
+                issues.push(PrecisionIssue {
+                    line,
+                    value: value.to_string(),
+                    kind: PrecisionIssueKind::Expanded,
+                    message: format!("已展开为 {}", integer),
+                });
+                items.push(integer);
+            }
+            Checked::Truncated(message) => {
+                refused += 1;
+                issues.push(PrecisionIssue {
+                    line,
+                    value: value.to_string(),
+                    kind: PrecisionIssueKind::PrecisionLoss,
+                    message,
+                });
+                items.push(value.to_string());
+            }
+            Checked::Refused(message) => {
+                refused += 1;
+                issues.push(PrecisionIssue {
+                    line,
+                    value: value.to_string(),
+                    kind: PrecisionIssueKind::ScientificNotation,
+                    message,
+                });
+                items.push(value.to_string());
+            }
+        }
+    }
+
+    let delimiter = if uses_newlines(input) { "\n" } else { "," };
+    Ok(PrecisionReport {
+        output: items.join(delimiter),
+        issues,
+        refused,
+    })
+}
+
+// 展开可还原的科学计数法；只要有无法还原或疑似被截断的条目就拒绝，并列出所在行
+// allow_trailing_zeros为true时放行末尾全为0的长ID，用于确认这些ID本来就是如此的情况
+pub fn repair_precision(input: &str, allow_trailing_zeros: bool) -> AppResult<String> {
+    let report = check_precision(input)?;
+    let refused: Vec<&PrecisionIssue> = report
+        .issues
+        .iter()
+        .filter(|issue| match issue.kind {
+            PrecisionIssueKind::Expanded => false,
+            PrecisionIssueKind::ScientificNotation => true,
+            PrecisionIssueKind::PrecisionLoss => !allow_trailing_zeros,
+        })
+        .collect();
+    if refused.is_empty() {
+        return Ok(report.output);
+    }
+
+    let issues = refused
+        .iter()
+        .map(|issue| (issue.line, issue.value.as_str(), issue.message.as_str()));
+    Err(AppError::InvalidInput(format!(
+        "发现疑似被Excel损坏的ID，请从原始数据重新导出:\n{}",
+        format_line_issues(issues)
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_scientific() {
+        assert_eq!(check_precision("1.23456789012E+11").unwrap().output, "123456789012");
+        assert_eq!(check_precision("1.20e2").unwrap().output, "120");
+
+        let report = check_precision("12\n1.23457E+17\n1.5E-1").unwrap();
+        assert_eq!(report.refused, 2);
+        assert_eq!(report.issues[0].line, 2);
+        assert_eq!(report.issues[0].kind, PrecisionIssueKind::ScientificNotation);
+        assert_eq!(report.issues[1].line, 3);
+    }
+
+    #[test]
+    fn test_huge_exponent_is_refused() {
+        let report = check_precision("1E+9999999999
+1E+99999999999999999999").unwrap();
+        assert_eq!(report.refused, 2);
+        assert!(report.issues.iter().all(|issue| issue.message.contains("超过64位")));
+        assert!(repair_precision("1E+9999999999", true).is_err());
+    }
+
+    #[test]
+    fn test_precision_loss() {
+        // 末尾全为0的长ID默认拒绝，明确放行时保留原值
+        let report = check_precision("123456789012345000\n123456789012345678").unwrap();
+        assert_eq!(report.refused, 1);
+        assert_eq!(report.issues[0].kind, PrecisionIssueKind::PrecisionLoss);
+        assert_eq!(report.issues[0].line, 1);
+        let err = repair_precision("1\n123456789012345000", false).unwrap_err().to_string();
+        assert!(err.contains("第 2 行"));
+        assert_eq!(repair_precision("1234567890123450\n1", true).unwrap(), "1234567890123450\n1");
+        assert!(repair_precision("1234567890123450\n1.23457E+17", true).is_err());
+
+        // 15位以内的末尾0不算精度丢失
+        assert_eq!(check_precision("100000").unwrap().refused, 0);
+    }
+
+    #[test]
+    fn test_repair_precision() {
+        assert_eq!(repair_precision("1,1.2E+1", false).unwrap(), "1,12");
+        let err = repair_precision("1\n2\n1.23457E+17", false).unwrap_err().to_string();
+        assert!(err.contains("第 3 行"));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::error::{AppError, AppResult};
+use crate::text_processor::{split_items_with_lines, uses_newlines};
+use serde::{Deserialize, Serialize};
+
+const DECIMAL: &str = "0123456789";
+const BINARY: &str = "01";
+const HEX: &str = "0123456789abcdef";
+const BASE36: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE62: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+// 进制
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "base", rename_all = "snake_case")]
+pub enum NumberBase {
+    Decimal,
+    Binary,
+    // 输出小写，输入不区分大小写，可带0x前缀
+    Hex,
+    // 0-9a-z，输入不区分大小写
+    Base36,
+    // 0-9A-Za-z
+    Base62,
+    // 自定义字母表，第一个字符代表0
+    Custom { alphabet: String },
+}
+
+impl NumberBase {
+    fn alphabet(&self) -> AppResult<Vec<char>> {
+        let alphabet: Vec<char> = match self {
+            NumberBase::Decimal => DECIMAL.chars().collect(),
+            NumberBase::Binary => BINARY.chars().collect(),
+            NumberBase::Hex => HEX.chars().collect(),
+            NumberBase::Base36 => BASE36.chars().collect(),
+            NumberBase::Base62 => BASE62.chars().collect(),
+            NumberBase::Custom { alphabet } => alphabet.chars().collect(),
+        };
+        if alphabet.len() < 2 {
+            return Err(AppError::InvalidInput("字母表至少需要2个字符".to_string()));
+        }
+        if let Some((i, c)) = alphabet.iter().enumerate().find(|(i, c)| alphabet[..*i].contains(c)) {
+            return Err(AppError::InvalidInput(format!("字母表第 {} 个字符 '{}' 重复", i + 1, c)));
+        }
+        Ok(alphabet)
+    }
+
+    // 十六进制和36进制输入不区分大小写
+    fn case_insensitive(&self) -> bool {
+        matches!(self, NumberBase::Hex | NumberBase::Base36)
+    }
+}
+
+// 进制转换选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RadixOptions {
+    pub from: NumberBase,
+    pub to: NumberBase,
+}
+
+// 转换失败的条目
+#[derive(Debug, Clone, Serialize)]
+pub struct RadixFailure {
+    pub line: usize,
+    pub value: String,
+    pub error: String,
+}
+
+// 转换结果；失败的条目原样保留在输出中
+#[derive(Debug, Clone, Serialize)]
+pub struct RadixReport {
+    pub output: String,
+    pub failures: Vec<RadixFailure>,
+}
+
+// 任意精度转换：在源进制的数字序列上反复除以目标进制取余。
+// 与encrypt_number对超出u64的纯数字ID的处理一致，超大数值同样有效，且不会丢失精度
+pub fn convert_radix(value: &str, from: &NumberBase, to: &NumberBase) -> AppResult<String> {
+    let from_alphabet = from.alphabet()?;
+    let to_alphabet = to.alphabet()?;
+
+    let mut value = value.trim();
+    if *from == NumberBase::Hex {
+        value = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")).unwrap_or(value);
+    } else if *from == NumberBase::Binary {
+        value = value.strip_prefix("0b").unwrap_or(value);
+    }
+    if value.is_empty() {
+        return Err(AppError::InvalidInput("输入不能为空".to_string()));
+    }
+
+    let from_radix = from_alphabet.len() as u32;
+    let to_radix = to_alphabet.len() as u32;
+    let mut digits = value
+        .chars()
+        .map(|c| {
+            let c = if from.case_insensitive() { c.to_ascii_lowercase() } else { c };
+            from_alphabet
+                .iter()
+                .position(|d| *d == c)
+                .map(|i| i as u32)
+                .ok_or_else(|| AppError::InvalidInput(format!("字符 '{}' 不属于源进制", c)))
+        })
+        .collect::<AppResult<Vec<u32>>>()?;
+
+    let mut output = Vec::new();
+    while digits.iter().any(|d| *d != 0) {
+        let mut remainder = 0u64;
+        let mut quotient = Vec::with_capacity(digits.len());
+        for digit in &digits {
+            let current = remainder * from_radix as u64 + *digit as u64;
+            let q = (current / to_radix as u64) as u32;
+            remainder = current % to_radix as u64;
+            if !quotient.is_empty() || q != 0 {
+                quotient.push(q);
+            }
+        }
+        output.push(to_alphabet[remainder as usize]);
+        digits = quotient;
+    }
+    if output.is_empty() {
+        output.push(to_alphabet[0]);
+    }
+    Ok(output.iter().rev().collect())
+}
+
+// 批量进制转换，保留原分隔符
+pub fn convert_radix_list(input: &str, options: &RadixOptions) -> AppResult<RadixReport> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+    // 先检查字母表，避免每一行都报同样的错误
+    options.from.alphabet()?;
+    options.to.alphabet()?;
+
+    let mut items = Vec::new();
+    let mut failures = Vec::new();
+    for (line, value) in split_items_with_lines(input) {
+        match convert_radix(value, &options.from, &options.to) {
+            Ok(converted) => items.push(converted),
+            Err(e) => {
+                failures.push(RadixFailure {
+                    line,
+                    value: value.to_string(),
+                    error: e.to_string(),
+                });
+                items.push(value.to_string());
+            }
+        }
+    }
+
+    let delimiter = if uses_newlines(input) { "\n" } else { "," };
+    Ok(RadixReport {
+        output: items.join(delimiter),
+        failures,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_radix() {
+        assert_eq!(convert_radix("255", &NumberBase::Decimal, &NumberBase::Hex).unwrap(), "ff");
+        assert_eq!(convert_radix("0xFF", &NumberBase::Hex, &NumberBase::Binary).unwrap(), "11111111");
+        assert_eq!(convert_radix("0", &NumberBase::Decimal, &NumberBase::Base62).unwrap(), "0");
+        assert_eq!(convert_radix("ZZ", &NumberBase::Base36, &NumberBase::Decimal).unwrap(), "1295");
+        assert_eq!(convert_radix("61", &NumberBase::Decimal, &NumberBase::Base62).unwrap(), "z");
+
+        let custom = NumberBase::Custom { alphabet: "ab".to_string() };
+        assert_eq!(convert_radix("5", &NumberBase::Decimal, &custom).unwrap(), "bab");
+        let duplicated = NumberBase::Custom { alphabet: "aba".to_string() };
+        assert!(convert_radix("5", &NumberBase::Decimal, &duplicated).is_err());
+    }
+
+    #[test]
+    fn test_beyond_u64() {
+        let big = "123456789012345678901234567890";
+        let base62 = convert_radix(big, &NumberBase::Decimal, &NumberBase::Base62).unwrap();
+        assert_eq!(convert_radix(&base62, &NumberBase::Base62, &NumberBase::Decimal).unwrap(), big);
+        assert_eq!(
+            convert_radix("18446744073709551616", &NumberBase::Decimal, &NumberBase::Hex).unwrap(),
+            "10000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_convert_radix_list() {
+        let options = RadixOptions {
+            from: NumberBase::Decimal,
+            to: NumberBase::Base36,
+        };
+        let report = convert_radix_list("35,36,abc", &options).unwrap();
+        assert_eq!(report.output, "z,10,abc");
+        assert_eq!(report.failures.len(), 1);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::crypto::{decrypt_batch, encrypt_batch};
+use crate::error::{AppError, AppResult};
+use crate::mask::{mask_list, MaskOptions};
+use crate::phone::{normalize_phones, PhoneOptions};
+use crate::sanitize::sanitize_text;
+use crate::sql::{generate_sql, SqlOptions};
+use crate::template::{render_template, TemplateOptions};
+use crate::text_processor::{
+    add_quotes, convert_format, dedupe, remove_quotes, replace_chinese_commas, split_items,
+    QuoteOptions,
+};
+use crate::store::{Named, NamedStore};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// 配方保存在应用数据目录下的 recipes.json
+const RECIPES: NamedStore = NamedStore::new("recipes.json", "配方");
+
+// 配方中的单个步骤
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum RecipeStep {
+    Sanitize,
+    ReplaceCommas,
+    ConvertFormat,
+    Dedupe,
+    NormalizePhones { options: PhoneOptions },
+    Encrypt,
+    Decrypt,
+    AddQuotes { options: QuoteOptions },
+    RemoveQuotes { options: QuoteOptions },
+    Mask { options: MaskOptions },
+    GenerateSql { options: SqlOptions },
+    RenderTemplate { options: TemplateOptions },
+}
+
+impl RecipeStep {
+    // 步骤的显示名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecipeStep::Sanitize => "清理不可见字符",
+            RecipeStep::ReplaceCommas => "替换中文逗号",
+            RecipeStep::ConvertFormat => "格式转换",
+            RecipeStep::Dedupe => "去重",
+            RecipeStep::NormalizePhones { .. } => "规范化手机号",
+            RecipeStep::Encrypt => "加密",
+            RecipeStep::Decrypt => "解密",
+            RecipeStep::AddQuotes { .. } => "添加引号",
+            RecipeStep::RemoveQuotes { .. } => "移除引号",
+            RecipeStep::Mask { .. } => "脱敏",
+            RecipeStep::GenerateSql { .. } => "生成SQL",
+            RecipeStep::RenderTemplate { .. } => "模板渲染",
+        }
+    }
+
+    // 执行单个步骤
+    pub fn apply(&self, input: &str) -> AppResult<String> {
+        match self {
+            RecipeStep::Sanitize => Ok(sanitize_text(input)),
+            RecipeStep::ReplaceCommas => replace_chinese_commas(input),
+            RecipeStep::ConvertFormat => convert_format(input),
+            RecipeStep::Dedupe => dedupe(input),
+            RecipeStep::NormalizePhones { options } => normalize_phones(input, options),
+            RecipeStep::Encrypt => encrypt_batch(input, false),
+            RecipeStep::Decrypt => decrypt_batch(input),
+            RecipeStep::AddQuotes { options } => add_quotes(input, options),
+            RecipeStep::RemoveQuotes { options } => remove_quotes(input, options),
+            RecipeStep::Mask { options } => mask_list(input, options),
+            RecipeStep::GenerateSql { options } => generate_sql(input, options),
+            RecipeStep::RenderTemplate { options } => render_template(input, options),
+        }
+    }
+}
+
+// 一个命名的配方：按顺序执行的步骤列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recipe {
+    pub name: String,
+    pub steps: Vec<RecipeStep>,
+}
+
+// 单个步骤的执行结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepReport {
+    pub step: String,
+    pub input_items: usize,
+    pub output_items: usize,
+}
+
+// 失败的步骤
+#[derive(Debug, Clone, Serialize)]
+pub struct StepFailure {
+    // 步骤序号，从1开始
+    pub index: usize,
+    pub step: String,
+    pub error: String,
+}
+
+// 整个配方的执行结果；某一步失败时steps只包含之前完成的步骤，output为失败步骤的输入
+#[derive(Debug, Clone, Serialize)]
+pub struct RecipeReport {
+    pub output: String,
+    pub steps: Vec<StepReport>,
+    pub failure: Option<StepFailure>,
+}
+
+// 依次执行配方中的步骤，某一步失败时停止，并在报告中记录失败的步骤和已完成步骤的统计
+pub fn run_recipe(recipe: &Recipe, input: &str) -> AppResult<RecipeReport> {
+    if recipe.steps.is_empty() {
+        return Err(AppError::InvalidInput(format!("配方 '{}' 没有任何步骤", recipe.name)));
+    }
+
+    let mut current = input.to_string();
+    let mut steps = Vec::with_capacity(recipe.steps.len());
+    for (i, step) in recipe.steps.iter().enumerate() {
+        let input_items = split_items(&current).len();
+        current = match step.apply(&current) {
+            Ok(output) => output,
+            Err(e) => {
+                let failure = StepFailure {
+                    index: i + 1,
+                    step: step.label().to_string(),
+                    error: e.to_string(),
+                };
+                return Ok(RecipeReport { output: current, steps, failure: Some(failure) });
+            }
+        };
+        steps.push(StepReport {
+            step: step.label().to_string(),
+            input_items,
+            output_items: split_items(&current).len(),
+        });
+    }
+
+    Ok(RecipeReport { output: current, steps, failure: None })
+}
+
+impl Named for Recipe {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+// 读取已保存的配方，文件不存在时返回空列表
+pub fn load_recipes(dir: &Path) -> AppResult<Vec<Recipe>> {
+    RECIPES.load(dir)
+}
+
+// 根据名称查找配方
+pub fn find_recipe(dir: &Path, name: &str) -> AppResult<Recipe> {
+    RECIPES.find(dir, name)
+}
+
+// 保存配方，同名配方会被覆盖
+pub fn save_recipe(dir: &Path, recipe: Recipe) -> AppResult<()> {
+    if recipe.name.trim().is_empty() {
+        return Err(AppError::InvalidInput("配方名称不能为空".to_string()));
+    }
+    RECIPES.save(dir, recipe)
+}
+
+// 删除配方
+pub fn delete_recipe(dir: &Path, name: &str) -> AppResult<()> {
+    RECIPES.delete::<Recipe>(dir, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::lock_config_for_test;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_run_recipe_reports_counts() {
+        let _guard = lock_config_for_test();
+        let recipe = Recipe {
+            name: "日常".to_string(),
+            steps: vec![
+                RecipeStep::ReplaceCommas,
+                RecipeStep::ConvertFormat,
+                RecipeStep::Dedupe,
+                RecipeStep::Encrypt,
+                RecipeStep::AddQuotes { options: QuoteOptions::default() },
+            ],
+        };
+        let report = run_recipe(&recipe, "3，1，3").unwrap();
+        let counts: Vec<(usize, usize)> = report
+            .steps
+            .iter()
+            .map(|s| (s.input_items, s.output_items))
+            .collect();
+        assert_eq!(counts, vec![(1, 3), (3, 3), (3, 2), (2, 2), (2, 2)]);
+        assert!(report.output.starts_with('\''));
+        assert!(report.failure.is_none());
+    }
+
+    #[test]
+    fn test_run_recipe_names_failing_step() {
+        let _guard = lock_config_for_test();
+        let recipe = Recipe {
+            name: "坏配方".to_string(),
+            steps: vec![RecipeStep::Dedupe, RecipeStep::Encrypt, RecipeStep::ConvertFormat],
+        };
+        let report = run_recipe(&recipe, "abc\nabc").unwrap();
+        assert_eq!(report.steps.len(), 1);
+        assert_eq!((report.steps[0].input_items, report.steps[0].output_items), (2, 1));
+        assert_eq!(report.output, "abc");
+        let failure = report.failure.unwrap();
+        assert_eq!((failure.index, failure.step.as_str()), (2, "加密"));
+        assert!(!failure.error.is_empty());
+    }
+
+    #[test]
+    fn test_save_load_delete() {
+        let temp = TempDir::new("recipe");
+        let dir = temp.path();
+        let recipe = Recipe {
+            name: "去重".to_string(),
+            steps: vec![RecipeStep::Dedupe],
+        };
+        save_recipe(dir, recipe.clone()).unwrap();
+        save_recipe(dir, recipe).unwrap();
+        assert_eq!(load_recipes(dir).unwrap().len(), 1);
+        assert_eq!(find_recipe(dir, "去重").unwrap().steps.len(), 1);
+
+        delete_recipe(dir, "去重").unwrap();
+        assert!(load_recipes(dir).unwrap().is_empty());
+        assert!(delete_recipe(dir, "去重").is_err());
+    }
+}
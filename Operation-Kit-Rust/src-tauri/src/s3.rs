@@ -0,0 +1,291 @@
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE, LAST_MODIFIED};
+use reqwest::{Method, Response, StatusCode};
+use chrono::{DateTime, Utc};
+use crate::oss::{canonical_query, hmac_sha256, uri_encode};
+use crate::storage::{content_type, normalize_last_modified, parse_list_xml, ObjectInfo, StorageBackend};
+use crate::target::OssTarget;
+use crate::vault::CredentialSecret;
+
+// SigV4签名算法名称
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+// 未填写地域时使用的默认值，MinIO默认也是这个地域
+const DEFAULT_REGION: &str = "us-east-1";
+
+// 构建SigV4规范请求，返回规范请求和参与签名的头名称列表
+// 格式: VERB + "\n" + CanonicalURI + "\n" + CanonicalQueryString + "\n" + CanonicalHeaders + "\n" + SignedHeaders + "\n" + HashedPayload
+fn canonical_request(
+    verb: &str,
+    uri: &str,
+    query: &[(&str, String)],
+    headers: &[(String, String)],
+    payload_hash: &str,
+) -> (String, String) {
+    let mut headers: Vec<(String, &str)> = headers
+        .iter()
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim()))
+        .collect();
+    headers.sort();
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        verb,
+        uri,
+        canonical_query(query),
+        canonical_headers,
+        signed_headers,
+        payload_hash
+    );
+    (request, signed_headers)
+}
+
+// 签名范围：日期/地域/s3/aws4_request
+fn scope(now: DateTime<Utc>, region: &str) -> String {
+    format!("{}/{}/s3/aws4_request", now.format("%Y%m%d"), region)
+}
+
+// 构建待签名字符串
+// 格式: "AWS4-HMAC-SHA256" + "\n" + 时间戳 + "\n" + 签名范围 + "\n" + hex(SHA256(CanonicalRequest))
+fn string_to_sign(now: DateTime<Utc>, region: &str, canonical_request: &str) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        ALGORITHM,
+        now.format("%Y%m%dT%H%M%SZ"),
+        scope(now, region),
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    )
+}
+
+// 签名密钥由 "AWS4" + SecretKey 依次对日期、地域、"s3"、"aws4_request" 做HMAC派生
+fn sign(secret_key: &str, now: DateTime<Utc>, region: &str, string_to_sign: &str) -> Result<String> {
+    let mut signing_key = hmac_sha256(
+        format!("AWS4{}", secret_key).as_bytes(),
+        &now.format("%Y%m%d").to_string(),
+    )?;
+    for part in [region, "s3", "aws4_request"] {
+        signing_key = hmac_sha256(&signing_key, part)?;
+    }
+    Ok(hex::encode(hmac_sha256(&signing_key, string_to_sign)?))
+}
+
+async fn s3_error(response: Response) -> anyhow::Error {
+    let status = response.status();
+    let error_text = response.text().await
+        .unwrap_or_else(|_| "无法获取错误详情".to_string());
+
+    if error_text.contains("InvalidAccessKeyId") {
+        anyhow!("Access ID无效，请检查配置")
+    } else if error_text.contains("SignatureDoesNotMatch") {
+        anyhow!("Access Key无效，请检查配置或签名错误")
+    } else if error_text.contains("ExpiredToken") || error_text.contains("InvalidToken") {
+        anyhow!("STS Security Token无效或已过期，请更新凭证")
+    } else {
+        anyhow!("S3请求失败: HTTP {}, 错误: {}", status, error_text)
+    }
+}
+
+// S3兼容存储后端，使用路径风格访问：endpoint/bucket/key
+pub struct S3Backend {
+    target: OssTarget,
+    credential: CredentialSecret,
+}
+
+impl S3Backend {
+    pub fn new(target: OssTarget, credential: CredentialSecret) -> Self {
+        Self { target, credential }
+    }
+
+    // endpoint未写协议时使用https，返回 (协议, 主机[:端口])
+    fn endpoint(&self) -> (&str, &str) {
+        let endpoint = self.target.endpoint.trim().trim_end_matches('/');
+        if let Some(host) = endpoint.strip_prefix("http://") {
+            ("http", host)
+        } else {
+            ("https", endpoint.strip_prefix("https://").unwrap_or(endpoint))
+        }
+    }
+
+    fn region(&self) -> &str {
+        match self.target.region.trim() {
+            "" => DEFAULT_REGION,
+            region => region,
+        }
+    }
+
+    // 签名并发送请求；key为空时访问Bucket本身（用于列举对象）
+    async fn send(&self, method: Method, key: &str, query: &[(&str, String)], body: Vec<u8>) -> Result<Response> {
+        let access_id = self.credential.access_id();
+        let secret_key = self.credential.access_key();
+        if access_id.trim().is_empty() || secret_key.trim().is_empty() {
+            return Err(anyhow!("S3 Access ID或Access Key不能为空"));
+        }
+
+        let (scheme, host) = self.endpoint();
+        let region = self.region();
+        let now = Utc::now();
+        let uri = format!("/{}/{}", uri_encode(&self.target.bucket, true), uri_encode(key, false));
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let mut signed_headers = vec![
+            ("host".to_string(), host.to_string()),
+            ("x-amz-content-sha256".to_string(), payload_hash.clone()),
+            ("x-amz-date".to_string(), now.format("%Y%m%dT%H%M%SZ").to_string()),
+        ];
+        if let Some(token) = self.credential.security_token() {
+            signed_headers.push(("x-amz-security-token".to_string(), token.to_string()));
+        }
+        let (request, signed_names) = canonical_request(method.as_str(), &uri, query, &signed_headers, &payload_hash);
+        let signature = sign(secret_key, now, region, &string_to_sign(now, region, &request))?;
+        let authorization = format!(
+            "{} Credential={}/{},SignedHeaders={},Signature={}",
+            ALGORITHM,
+            access_id,
+            scope(now, region),
+            signed_names,
+            signature
+        );
+
+        let mut url = format!("{}://{}{}", scheme, host, uri);
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&canonical_query(query));
+        }
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &signed_headers {
+            headers.insert(HeaderName::from_bytes(name.as_bytes())?, HeaderValue::from_str(value)?);
+        }
+        if method == Method::PUT {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_str(content_type(key))?);
+            headers.insert(CONTENT_LENGTH, HeaderValue::from_str(&body.len().to_string())?);
+        }
+        headers.insert("Authorization", HeaderValue::from_str(&authorization)?);
+
+        reqwest::Client::new()
+            .request(method, &url)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| anyhow!("发送请求失败: {}", e))
+    }
+}
+
+impl StorageBackend for S3Backend {
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        let response = self.send(Method::PUT, key, &[], body).await?;
+        if !response.status().is_success() {
+            return Err(s3_error(response).await);
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let response = self.send(Method::GET, key, &[], Vec::new()).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(anyhow!("对象不存在: {}", key));
+        }
+        if !response.status().is_success() {
+            return Err(s3_error(response).await);
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    // ListObjects（v1），以上一页最后一个key作为marker翻页
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        let mut objects = Vec::new();
+        loop {
+            let mut query = vec![("prefix", prefix.to_string()), ("max-keys", "1000".to_string())];
+            if let Some(last) = objects.last().map(|o: &ObjectInfo| o.key.clone()) {
+                query.push(("marker", last));
+            }
+            let response = self.send(Method::GET, "", &query, Vec::new()).await?;
+            if !response.status().is_success() {
+                return Err(s3_error(response).await);
+            }
+            let (page, truncated) = parse_list_xml(&response.text().await?)?;
+            let empty = page.is_empty();
+            objects.extend(page);
+            if !truncated || empty {
+                return Ok(objects);
+            }
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let response = self.send(Method::DELETE, key, &[], Vec::new()).await?;
+        if !response.status().is_success() {
+            return Err(s3_error(response).await);
+        }
+        Ok(())
+    }
+
+    async fn head(&self, key: &str) -> Result<Option<ObjectInfo>> {
+        let response = self.send(Method::HEAD, key, &[], Vec::new()).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(s3_error(response).await);
+        }
+        let header = |name| response.headers().get(name).and_then(|v| v.to_str().ok());
+        Ok(Some(ObjectInfo {
+            key: key.to_string(),
+            size: header(CONTENT_LENGTH).and_then(|v| v.parse().ok()).unwrap_or(0),
+            last_modified: header(LAST_MODIFIED).map(normalize_last_modified),
+        }))
+    }
+
+    fn location(&self, key: &str) -> String {
+        let (scheme, host) = self.endpoint();
+        format!("S3 路径: {}://{}/{}/{}", scheme, host, self.target.bucket, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sigv4_get_object_example() {
+        // AWS SigV4文档中 GET /test.txt 的示例
+        let now = DateTime::parse_from_rfc3339("2013-05-24T00:00:00Z").unwrap().with_timezone(&Utc);
+        let empty_hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let headers = vec![
+            ("Host".to_string(), "examplebucket.s3.amazonaws.com".to_string()),
+            ("Range".to_string(), "bytes=0-9".to_string()),
+            ("x-amz-content-sha256".to_string(), empty_hash.to_string()),
+            ("x-amz-date".to_string(), "20130524T000000Z".to_string()),
+        ];
+        let (request, signed_headers) = canonical_request("GET", "/test.txt", &[], &headers, empty_hash);
+        assert_eq!(signed_headers, "host;range;x-amz-content-sha256;x-amz-date");
+        assert_eq!(
+            string_to_sign(now, "us-east-1", &request),
+            "AWS4-HMAC-SHA256\n20130524T000000Z\n20130524/us-east-1/s3/aws4_request\n\
+             7344ae5b7ee6c3e7e6b0fe0640412a37625d1fbfff95c48bbb2dc43964946972"
+        );
+        let signature = sign(
+            "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY",
+            now,
+            "us-east-1",
+            &string_to_sign(now, "us-east-1", &request),
+        )
+        .unwrap();
+        assert_eq!(signature, "f0e8bdb87c964420e857bd35b5d6ed310bd44f0170aba48dd91039c6036bdb41");
+    }
+
+    #[test]
+    fn test_list_query_is_canonicalized() {
+        let query = [("prefix", "tmp ids/".to_string()), ("max-keys", "1000".to_string())];
+        assert_eq!(canonical_query(&query), "max-keys=1000&prefix=tmp%20ids%2F");
+    }
+}
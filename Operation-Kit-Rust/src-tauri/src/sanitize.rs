@@ -0,0 +1,160 @@
+use serde::Serialize;
+
+// 对不可见字符的处理方式
+enum Action {
+    Remove,
+    // 替换为普通空格，交给trim处理
+    Space,
+}
+
+// 识别不可见/控制字符，返回名称和处理方式；换行和制表符属于正常分隔符，不处理
+// \r 也会被报告并删除，分行时它留在所在行的末尾，输出统一用 \n 换行
+fn classify(c: char) -> Option<(&'static str, Action)> {
+    let found = match c {
+        '\n' | '\t' => return None,
+        '\r' => ("回车符", Action::Remove),
+        '\u{00A0}' => ("不换行空格", Action::Space),
+        '\u{3000}' => ("全角空格", Action::Space),
+        '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' => ("特殊宽度空格", Action::Space),
+        '\u{200B}' => ("零宽空格", Action::Remove),
+        '\u{200C}' => ("零宽非连接符", Action::Remove),
+        '\u{200D}' => ("零宽连接符", Action::Remove),
+        '\u{2060}' => ("零宽不换行符", Action::Remove),
+        '\u{FEFF}' => ("BOM", Action::Remove),
+        '\u{00AD}' => ("软连字符", Action::Remove),
+        '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => {
+            ("文字方向控制符", Action::Remove)
+        }
+        c if c.is_control() => ("控制字符", Action::Remove),
+        _ => return None,
+    };
+    Some(found)
+}
+
+// 某一行中发现的一种不可见字符
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InvisibleChar {
+    pub code: String,
+    pub name: String,
+    pub count: usize,
+}
+
+// 某一行的检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SanitizeLine {
+    pub line: usize,
+    pub chars: Vec<InvisibleChar>,
+}
+
+// 清理结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SanitizeReport {
+    pub output: String,
+    pub lines: Vec<SanitizeLine>,
+    pub removed: usize,
+}
+
+// 清理一行，返回清理后的文本和发现的字符
+fn sanitize_line(line: &str) -> (String, Vec<InvisibleChar>) {
+    let mut output = String::with_capacity(line.len());
+    let mut found: Vec<InvisibleChar> = Vec::new();
+    for c in line.chars() {
+        let Some((name, action)) = classify(c) else {
+            output.push(c);
+            continue;
+        };
+        if let Action::Space = action {
+            output.push(' ');
+        }
+        let code = format!("U+{:04X}", c as u32);
+        match found.iter_mut().find(|f| f.code == code) {
+            Some(existing) => existing.count += 1,
+            None => found.push(InvisibleChar {
+                code,
+                name: name.to_string(),
+                count: 1,
+            }),
+        }
+    }
+    (output, found)
+}
+
+// 按 \n、\r\n 和单独的 \r 分行，避免只用 \r 换行的文本被并成一行；
+// 行尾的 \r 保留在该行中，先按不可见字符报告，再随清理去掉
+fn split_lines(input: &str) -> impl Iterator<Item = &str> {
+    input.split('\n').flat_map(|piece| {
+        let mut lines: Vec<&str> = piece.split_inclusive('\r').collect();
+        if lines.is_empty() {
+            lines.push("");
+        }
+        lines
+    })
+}
+
+// 清理不可见字符并报告每一行发现了哪些字符
+pub fn sanitize_report(input: &str) -> SanitizeReport {
+    let mut output = Vec::new();
+    let mut lines = Vec::new();
+    let mut removed = 0;
+    for (i, line) in split_lines(input).enumerate() {
+        let (cleaned, chars) = sanitize_line(line);
+        if !chars.is_empty() {
+            removed += chars.iter().map(|c| c.count).sum::<usize>();
+            lines.push(SanitizeLine { line: i + 1, chars });
+        }
+        output.push(cleaned);
+    }
+    SanitizeReport {
+        output: output.join("\n"),
+        lines,
+        removed,
+    }
+}
+
+// 只返回清理后的文本
+pub fn sanitize_text(input: &str) -> String {
+    sanitize_report(input).output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_text() {
+        let input = "\u{FEFF}123\u{200B}\r\n456\u{00A0}\n7\t8";
+        assert_eq!(sanitize_text(input), "123\n456 \n7\t8");
+    }
+
+    #[test]
+    fn test_sanitize_report_lines() {
+        let report = sanitize_report("1\n2\u{200B}\u{200B}\n3\u{3000}\r");
+        assert_eq!(report.removed, 4);
+        assert_eq!(report.lines.len(), 2);
+        assert_eq!(report.lines[0].line, 2);
+        assert_eq!(
+            report.lines[0].chars,
+            vec![InvisibleChar {
+                code: "U+200B".to_string(),
+                name: "零宽空格".to_string(),
+                count: 2
+            }]
+        );
+        assert_eq!(report.lines[1].chars.len(), 2);
+    }
+
+    #[test]
+    fn test_line_endings_are_kept() {
+        // 单独的\r也是换行，不能把前后两行拼在一起
+        let report = sanitize_report("1\r2\r\n3\n4\u{200B}");
+        assert_eq!(report.output, "1\n2\n3\n4");
+        // 两个\r都要报告在各自的行上
+        assert_eq!(report.removed, 3);
+        let lines: Vec<(usize, &str)> = report
+            .lines
+            .iter()
+            .map(|l| (l.line, l.chars[0].name.as_str()))
+            .collect();
+        assert_eq!(lines, vec![(1, "回车符"), (2, "回车符"), (4, "零宽空格")]);
+    }
+}
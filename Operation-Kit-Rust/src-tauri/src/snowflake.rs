@@ -0,0 +1,206 @@
+use crate::error::{AppError, AppResult};
+use crate::text_processor::split_items_with_lines;
+use crate::timestamp::{parse_timezone, timestamp_to_datetime, TimeUnit};
+use serde::{Deserialize, Serialize};
+
+// 雪花ID的纪元和位布局；从高到低依次为 符号位|时间戳|数据中心|机器|序列号
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SnowflakeOptions {
+    // 纪元（毫秒时间戳），默认为Twitter的 2010-11-04 01:42:54.657 UTC
+    pub epoch_ms: i64,
+    pub timestamp_bits: u32,
+    pub datacenter_bits: u32,
+    pub worker_bits: u32,
+    pub sequence_bits: u32,
+    pub timezone: String,
+}
+
+impl Default for SnowflakeOptions {
+    fn default() -> Self {
+        Self {
+            epoch_ms: 1_288_834_974_657,
+            timestamp_bits: 41,
+            datacenter_bits: 5,
+            worker_bits: 5,
+            sequence_bits: 12,
+            timezone: "Asia/Shanghai".to_string(),
+        }
+    }
+}
+
+// 解析出的各字段
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SnowflakeId {
+    pub line: usize,
+    pub id: String,
+    pub timestamp: i64,
+    pub time: String,
+    pub datacenter: u64,
+    pub worker: u64,
+    pub sequence: u64,
+}
+
+// 无法解析的ID
+#[derive(Debug, Clone, Serialize)]
+pub struct SnowflakeFailure {
+    pub line: usize,
+    pub value: String,
+    pub error: String,
+}
+
+// 解析结果；output为制表符分隔的表格，可直接粘贴到Excel
+#[derive(Debug, Clone, Serialize)]
+pub struct SnowflakeReport {
+    pub output: String,
+    pub ids: Vec<SnowflakeId>,
+    pub failures: Vec<SnowflakeFailure>,
+}
+
+// 取出从shift开始的bits位
+fn field(id: u64, shift: u32, bits: u32) -> u64 {
+    if bits == 0 {
+        0
+    } else {
+        (id >> shift) & ((1u64 << bits) - 1)
+    }
+}
+
+// 各字段的起始位和总位数：(机器, 数据中心, 时间戳, 合计)；位数来自用户输入，相加时检查溢出
+fn layout(options: &SnowflakeOptions) -> AppResult<(u32, u32, u32, u32)> {
+    let overflow = || AppError::InvalidInput("位布局无效：位数之和超出范围".to_string());
+    let worker_shift = options.sequence_bits;
+    let datacenter_shift = worker_shift.checked_add(options.worker_bits).ok_or_else(overflow)?;
+    let timestamp_shift = datacenter_shift.checked_add(options.datacenter_bits).ok_or_else(overflow)?;
+    let total_bits = timestamp_shift.checked_add(options.timestamp_bits).ok_or_else(overflow)?;
+    Ok((worker_shift, datacenter_shift, timestamp_shift, total_bits))
+}
+
+fn decode_one(line: usize, value: &str, options: &SnowflakeOptions, tz: chrono_tz::Tz) -> AppResult<SnowflakeId> {
+    let id: u64 = value
+        .parse()
+        .map_err(|_| AppError::InvalidInput("不是有效的64位数字ID".to_string()))?;
+    let (worker_shift, datacenter_shift, timestamp_shift, total_bits) = layout(options)?;
+
+    // 位布局以外的高位（含符号位）必须为0
+    if id >> total_bits != 0 {
+        return Err(AppError::InvalidInput("ID超出了位布局允许的范围".to_string()));
+    }
+    let elapsed = field(id, timestamp_shift, options.timestamp_bits);
+    let timestamp = options
+        .epoch_ms
+        .checked_add(elapsed as i64)
+        .ok_or_else(|| AppError::InvalidInput("纪元加上时间偏移后超出了时间戳范围".to_string()))?;
+    let time = timestamp_to_datetime(timestamp, TimeUnit::Millis)?
+        .with_timezone(&tz)
+        .format("%Y-%m-%d %H:%M:%S%.3f")
+        .to_string();
+    Ok(SnowflakeId {
+        line,
+        id: value.to_string(),
+        timestamp,
+        time,
+        datacenter: field(id, datacenter_shift, options.datacenter_bits),
+        worker: field(id, worker_shift, options.worker_bits),
+        sequence: field(id, 0, options.sequence_bits),
+    })
+}
+
+// 批量解析雪花ID，得到生成时间、数据中心、机器和序列号
+pub fn decode_snowflakes(input: &str, options: &SnowflakeOptions) -> AppResult<SnowflakeReport> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+    let (_, _, _, total_bits) = layout(options)?;
+    if options.timestamp_bits == 0 || total_bits > 63 {
+        return Err(AppError::InvalidInput(format!(
+            "位布局无效：时间戳位数必须大于0，各部分合计不能超过63位（当前{}位）",
+            total_bits
+        )));
+    }
+    let tz = parse_timezone(&options.timezone)?;
+
+    let mut ids = Vec::new();
+    let mut failures = Vec::new();
+    for (line, value) in split_items_with_lines(input) {
+        match decode_one(line, value, options, tz) {
+            Ok(id) => ids.push(id),
+            Err(e) => failures.push(SnowflakeFailure {
+                line,
+                value: value.to_string(),
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    let mut rows = vec!["ID\t时间\t数据中心\t机器\t序列号".to_string()];
+    rows.extend(ids.iter().map(|id| {
+        format!("{}\t{}\t{}\t{}\t{}", id.id, id.time, id.datacenter, id.worker, id.sequence)
+    }));
+    Ok(SnowflakeReport {
+        output: rows.join("\n"),
+        ids,
+        failures,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_twitter_layout() {
+        // 按默认布局构造：时间偏移1000ms，数据中心3，机器7，序列号42
+        let id = (1000u64 << 22) | (3 << 17) | (7 << 12) | 42;
+        let options = SnowflakeOptions {
+            timezone: "UTC".to_string(),
+            ..SnowflakeOptions::default()
+        };
+        let report = decode_snowflakes(&format!("{}\nabc", id), &options).unwrap();
+        let decoded = &report.ids[0];
+        assert_eq!(decoded.timestamp, 1_288_834_975_657);
+        assert_eq!(decoded.time, "2010-11-04 01:42:55.657");
+        assert_eq!((decoded.datacenter, decoded.worker, decoded.sequence), (3, 7, 42));
+        assert_eq!(report.failures[0].line, 2);
+    }
+
+    #[test]
+    fn test_custom_layout() {
+        // 无数据中心字段，10位机器号
+        let options = SnowflakeOptions {
+            epoch_ms: 0,
+            datacenter_bits: 0,
+            worker_bits: 10,
+            ..SnowflakeOptions::default()
+        };
+        let id = (5u64 << 22) | (1023 << 12) | 1;
+        let report = decode_snowflakes(&id.to_string(), &options).unwrap();
+        assert_eq!(report.ids[0].timestamp, 5);
+        assert_eq!((report.ids[0].datacenter, report.ids[0].worker), (0, 1023));
+
+        let invalid = SnowflakeOptions {
+            timestamp_bits: 50,
+            ..SnowflakeOptions::default()
+        };
+        assert!(decode_snowflakes("1", &invalid).is_err());
+    }
+
+    #[test]
+    fn test_overflowing_options_are_rejected() {
+        let huge_bits = SnowflakeOptions {
+            worker_bits: u32::MAX,
+            ..SnowflakeOptions::default()
+        };
+        assert!(matches!(decode_snowflakes("1", &huge_bits), Err(AppError::InvalidInput(_))));
+
+        // 纪元接近i64上限时，加上时间偏移会溢出，应作为该行的失败报告
+        let huge_epoch = SnowflakeOptions {
+            epoch_ms: i64::MAX,
+            timezone: "UTC".to_string(),
+            ..SnowflakeOptions::default()
+        };
+        let report = decode_snowflakes(&(1u64 << 22).to_string(), &huge_epoch).unwrap();
+        assert!(report.ids.is_empty());
+        assert!(report.failures[0].error.contains("超出了时间戳范围"));
+    }
+}
@@ -0,0 +1,200 @@
+use crate::error::{AppError, AppResult};
+use crate::text_processor::split_items;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+// 压缩包中清单文件的名称
+const MANIFEST_FILE: &str = "manifest.json";
+
+// 拆分方式：每份固定行数，或固定份数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "by", rename_all = "snake_case")]
+pub enum SplitMode {
+    Size { size: usize },
+    Count { count: usize },
+}
+
+impl Default for SplitMode {
+    fn default() -> Self {
+        SplitMode::Size { size: 100_000 }
+    }
+}
+
+// 拆分选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SplitOptions {
+    #[serde(flatten)]
+    pub mode: SplitMode,
+    // 文件名前缀，生成 part_001.txt 这样的文件
+    pub prefix: String,
+    // 是否打包为zip（附带清单），而不是写出单独的文件
+    pub zip: bool,
+}
+
+impl Default for SplitOptions {
+    fn default() -> Self {
+        Self {
+            mode: SplitMode::default(),
+            prefix: "part".to_string(),
+            zip: false,
+        }
+    }
+}
+
+// 单个分片的信息
+#[derive(Debug, Clone, Serialize)]
+pub struct PartInfo {
+    pub file: String,
+    pub rows: usize,
+    pub md5: String,
+}
+
+// 拆分结果；打包时同时作为清单写入压缩包
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitReport {
+    pub total: usize,
+    pub parts: Vec<PartInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zip_file: Option<String>,
+}
+
+// 按行数或份数把条目分组；按份数拆分时尽量平均，前几份多一行
+pub fn split_chunks<'a>(items: &[&'a str], mode: SplitMode) -> AppResult<Vec<Vec<&'a str>>> {
+    match mode {
+        SplitMode::Size { size } => {
+            if size == 0 {
+                return Err(AppError::InvalidInput("每份行数必须大于0".to_string()));
+            }
+            Ok(items.chunks(size).map(<[&str]>::to_vec).collect())
+        }
+        SplitMode::Count { count } => {
+            if count == 0 {
+                return Err(AppError::InvalidInput("份数必须大于0".to_string()));
+            }
+            let count = count.min(items.len());
+            let (base, extra) = (items.len() / count, items.len() % count);
+            let mut chunks = Vec::with_capacity(count);
+            let mut start = 0;
+            for i in 0..count {
+                let end = start + base + usize::from(i < extra);
+                chunks.push(items[start..end].to_vec());
+                start = end;
+            }
+            Ok(chunks)
+        }
+    }
+}
+
+// 把列表拆分为多个文件写入目标文件夹，或打包为一个zip
+pub fn split_to_files(input: &str, dir: &Path, options: &SplitOptions) -> AppResult<SplitReport> {
+    let items = split_items(input);
+    if items.is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+    if options.prefix.trim().is_empty() || options.prefix.contains(['/', '\\']) {
+        return Err(AppError::InvalidInput("文件名前缀无效".to_string()));
+    }
+
+    let chunks = split_chunks(&items, options.mode)?;
+    // 序号至少3位，超过999份时自动加宽
+    let width = chunks.len().to_string().len().max(3);
+    let files: Vec<(String, String)> = chunks
+        .iter()
+        .enumerate()
+        .map(|(i, chunk)| (format!("{}_{:0width$}.txt", options.prefix, i + 1), chunk.join("\n")))
+        .collect();
+
+    let mut report = SplitReport {
+        total: items.len(),
+        parts: files
+            .iter()
+            .zip(&chunks)
+            .map(|((file, content), chunk)| PartInfo {
+                file: file.clone(),
+                rows: chunk.len(),
+                md5: format!("{:x}", md5::compute(content.as_bytes())),
+            })
+            .collect(),
+        zip_file: None,
+    };
+
+    fs::create_dir_all(dir)?;
+    // 不覆盖已有文件，避免和上一次拆分的结果混在一起
+    let targets: Vec<String> = if options.zip {
+        vec![format!("{}.zip", options.prefix)]
+    } else {
+        files.iter().map(|(file, _)| file.clone()).collect()
+    };
+    if let Some(existing) = targets.iter().find(|file| dir.join(file).exists()) {
+        return Err(AppError::InvalidInput(format!("目标文件夹中已存在 {}", existing)));
+    }
+
+    if options.zip {
+        let zip_name = targets[0].clone();
+        let mut writer = ZipWriter::new(File::create(dir.join(&zip_name))?);
+        let file_options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        for (file, content) in &files {
+            writer.start_file(file.as_str(), file_options)?;
+            writer.write_all(content.as_bytes())?;
+        }
+        writer.start_file(MANIFEST_FILE, file_options)?;
+        writer.write_all(serde_json::to_string_pretty(&report)?.as_bytes())?;
+        writer.finish()?;
+        report.zip_file = Some(zip_name);
+    } else {
+        for (file, content) in &files {
+            fs::write(dir.join(file), content)?;
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_split_chunks() {
+        let items = ["1", "2", "3", "4", "5"];
+        let by_size = split_chunks(&items, SplitMode::Size { size: 2 }).unwrap();
+        assert_eq!(by_size, vec![vec!["1", "2"], vec!["3", "4"], vec!["5"]]);
+
+        let by_count = split_chunks(&items, SplitMode::Count { count: 2 }).unwrap();
+        assert_eq!(by_count, vec![vec!["1", "2", "3"], vec!["4", "5"]]);
+
+        assert!(split_chunks(&items, SplitMode::Size { size: 0 }).is_err());
+    }
+
+    #[test]
+    fn test_split_to_files_and_zip() {
+        let temp = TempDir::new("split");
+        let dir = temp.path();
+        let options = SplitOptions {
+            mode: SplitMode::Size { size: 2 },
+            ..SplitOptions::default()
+        };
+        let report = split_to_files("1\n2\n3", dir, &options).unwrap();
+        assert_eq!(report.parts.len(), 2);
+        assert_eq!(report.parts[0].file, "part_001.txt");
+        assert_eq!(report.parts[1].rows, 1);
+        assert_eq!(report.parts[1].md5, format!("{:x}", md5::compute(b"3")));
+        assert_eq!(fs::read_to_string(dir.join("part_001.txt")).unwrap(), "1\n2");
+
+        // 已存在时拒绝覆盖
+        assert!(split_to_files("1\n2\n3", dir, &options).is_err());
+
+        let zipped = SplitOptions { zip: true, ..options };
+        let report = split_to_files("1\n2\n3", dir, &zipped).unwrap();
+        assert_eq!(report.zip_file.as_deref(), Some("part.zip"));
+        let archive = zip::ZipArchive::new(File::open(dir.join("part.zip")).unwrap()).unwrap();
+        assert_eq!(archive.len(), 3);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::error::{AppError, AppResult};
+use crate::text_processor::split_items;
+use serde::{Deserialize, Serialize};
+
+// Oracle 的 IN 列表最多允许 1000 个元素 (ORA-01795)
+const ORACLE_IN_LIMIT: usize = 1000;
+// 默认每个分块的元素数量
+const DEFAULT_CHUNK_SIZE: usize = 1000;
+// 双精度浮点数能精确表示的最大整数 2^53；MySQL和Hive把数字和字符串列比较时会转为double
+const MAX_EXACT_DOUBLE: u64 = 1 << 53;
+
+// 支持的SQL方言
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SqlDialect {
+    Mysql,
+    Hive,
+    Oracle,
+    #[serde(alias = "postgres")]
+    Postgresql,
+}
+
+impl SqlDialect {
+    // MySQL和Hive把反斜杠当作转义符，Oracle和PostgreSQL只认单引号重复
+    fn uses_backslash_escape(&self) -> bool {
+        matches!(self, SqlDialect::Mysql | SqlDialect::Hive)
+    }
+
+    // 将字符串转义为该方言下的字符串字面量
+    pub fn quote_literal(&self, value: &str) -> String {
+        let mut literal = String::with_capacity(value.len() + 2);
+        literal.push('\'');
+        for c in value.chars() {
+            match c {
+                '\'' if self.uses_backslash_escape() => literal.push_str("\\'"),
+                '\'' => literal.push_str("''"),
+                '\\' if self.uses_backslash_escape() => literal.push_str("\\\\"),
+                _ => literal.push(c),
+            }
+        }
+        literal.push('\'');
+        literal
+    }
+
+    // 不带FROM子句的SELECT在Oracle中需要DUAL
+    fn dual_suffix(&self) -> &'static str {
+        match self {
+            SqlDialect::Oracle => " FROM DUAL",
+            _ => "",
+        }
+    }
+}
+
+// 生成的SQL形式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SqlMode {
+    // column IN (...)
+    InClause,
+    // INSERT INTO table (column) VALUES (...), (...)
+    Values,
+    // SELECT ... UNION ALL SELECT ... 临时表
+    UnionAll,
+}
+
+// 数字条目是否加引号
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SqlQuoting {
+    // 全部是数字且都在 ±2^53 以内时不加引号，否则全部加引号
+    Auto,
+    // 全部加引号
+    Always,
+    // 全部是数字时不加引号，无论长短
+    Never,
+}
+
+// SQL生成选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SqlOptions {
+    pub dialect: SqlDialect,
+    pub mode: SqlMode,
+    pub column: String,
+    pub table: String,
+    pub chunk_size: usize,
+    // IN子句分块时是否用OR合并为一个条件
+    pub join_with_or: bool,
+    pub quoting: SqlQuoting,
+}
+
+impl Default for SqlOptions {
+    fn default() -> Self {
+        SqlOptions {
+            dialect: SqlDialect::Mysql,
+            mode: SqlMode::InClause,
+            column: "id".to_string(),
+            table: "tmp_ids".to_string(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            join_with_or: false,
+            quoting: SqlQuoting::Auto,
+        }
+    }
+}
+
+// 判断条目是否为可以不加引号的整数（带前导零的保留为字符串，避免丢失）
+fn is_numeric_literal(item: &str) -> bool {
+    let digits = item.strip_prefix('-').unwrap_or(item);
+    !digits.is_empty()
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'))
+}
+
+// 16位以上的ID和字符串列比较时会按double比较，超过2^53就会误匹配，需要加引号
+fn fits_exact_double(item: &str) -> bool {
+    item.trim_start_matches('-')
+        .parse::<u64>()
+        .is_ok_and(|value| value <= MAX_EXACT_DOUBLE)
+}
+
+// 将输入列表生成为SQL，按chunk_size分块，块之间以空行分隔
+pub fn generate_sql(input: &str, options: &SqlOptions) -> AppResult<String> {
+    let items = split_items(input);
+    if items.is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+    if options.column.trim().is_empty() {
+        return Err(AppError::InvalidInput("列名不能为空".to_string()));
+    }
+    if options.mode != SqlMode::InClause && options.table.trim().is_empty() {
+        return Err(AppError::InvalidInput("表名不能为空".to_string()));
+    }
+    if options.chunk_size == 0 {
+        return Err(AppError::InvalidInput("分块大小必须大于0".to_string()));
+    }
+    if options.dialect == SqlDialect::Oracle
+        && options.mode == SqlMode::InClause
+        && options.chunk_size > ORACLE_IN_LIMIT
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Oracle的IN列表最多{}个元素，请调小分块大小",
+            ORACLE_IN_LIMIT
+        )));
+    }
+
+    // 只要有一个条目需要引号，全部按字符串处理，保证同一列类型一致
+    let numeric = match options.quoting {
+        SqlQuoting::Auto => items.iter().all(|item| is_numeric_literal(item) && fits_exact_double(item)),
+        SqlQuoting::Always => false,
+        SqlQuoting::Never => items.iter().all(|item| is_numeric_literal(item)),
+    };
+    let literals: Vec<String> = items
+        .iter()
+        .map(|item| {
+            if numeric {
+                item.to_string()
+            } else {
+                options.dialect.quote_literal(item)
+            }
+        })
+        .collect();
+
+    let chunks: Vec<String> = literals
+        .chunks(options.chunk_size)
+        .map(|chunk| render_chunk(chunk, options))
+        .collect();
+
+    if options.mode == SqlMode::InClause && options.join_with_or && chunks.len() > 1 {
+        return Ok(format!("(\n  {}\n)", chunks.join("\n  OR ")));
+    }
+
+    Ok(chunks.join("\n\n"))
+}
+
+// 渲染单个分块
+fn render_chunk(literals: &[String], options: &SqlOptions) -> String {
+    let column = options.column.trim();
+    let table = options.table.trim();
+    let dialect = options.dialect;
+
+    match options.mode {
+        SqlMode::InClause => format!("{} IN ({})", column, literals.join(",")),
+        SqlMode::Values => match dialect {
+            // Oracle 23c 之前不支持多行VALUES，使用INSERT ALL
+            SqlDialect::Oracle => {
+                let rows: Vec<String> = literals
+                    .iter()
+                    .map(|v| format!("  INTO {} ({}) VALUES ({})", table, column, v))
+                    .collect();
+                format!("INSERT ALL\n{}\nSELECT 1 FROM DUAL;", rows.join("\n"))
+            }
+            SqlDialect::Hive => {
+                let rows: Vec<String> = literals.iter().map(|v| format!("({})", v)).collect();
+                format!("INSERT INTO TABLE {} VALUES\n{};", table, rows.join(",\n"))
+            }
+            SqlDialect::Mysql | SqlDialect::Postgresql => {
+                let rows: Vec<String> = literals.iter().map(|v| format!("({})", v)).collect();
+                format!("INSERT INTO {} ({}) VALUES\n{};", table, column, rows.join(",\n"))
+            }
+        },
+        SqlMode::UnionAll => {
+            let dual = dialect.dual_suffix();
+            let selects: Vec<String> = literals
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    // 只在第一行指定列别名
+                    if i == 0 {
+                        format!("SELECT {} AS {}{}", v, column, dual)
+                    } else {
+                        format!("SELECT {}{}", v, dual)
+                    }
+                })
+                .collect();
+            format!(
+                "WITH {} AS (\n  {}\n)\nSELECT {} FROM {};",
+                table,
+                selects.join("\n  UNION ALL "),
+                column,
+                table
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_in_clause() {
+        let options = SqlOptions::default();
+        let sql = generate_sql("1,2,3", &options).unwrap();
+        assert_eq!(sql, "id IN (1,2,3)");
+    }
+
+    #[test]
+    fn test_quoting_and_escaping() {
+        let mut options = SqlOptions::default();
+        let sql = generate_sql("a'b\nc\\d", &options).unwrap();
+        assert_eq!(sql, "id IN ('a\\'b','c\\\\d')");
+
+        options.dialect = SqlDialect::Postgresql;
+        let sql = generate_sql("a'b\nc\\d", &options).unwrap();
+        assert_eq!(sql, "id IN ('a''b','c\\d')");
+
+        // 带前导零的条目按字符串处理
+        let sql = generate_sql("007,8", &options).unwrap();
+        assert_eq!(sql, "id IN ('007','8')");
+    }
+
+    #[test]
+    fn test_long_ids_are_quoted() {
+        // 超过2^53的ID默认加引号，避免和字符串列按double比较时误匹配
+        let mut options = SqlOptions::default();
+        let sql = generate_sql("9007199254740992,1234567890123456789", &options).unwrap();
+        assert_eq!(sql, "id IN ('9007199254740992','1234567890123456789')");
+        assert_eq!(generate_sql("9007199254740992,-1", &options).unwrap(), "id IN (9007199254740992,-1)");
+
+        options.quoting = SqlQuoting::Never;
+        let sql = generate_sql("1234567890123456789", &options).unwrap();
+        assert_eq!(sql, "id IN (1234567890123456789)");
+        options.quoting = SqlQuoting::Always;
+        assert_eq!(generate_sql("1,2", &options).unwrap(), "id IN ('1','2')");
+    }
+
+    #[test]
+    fn test_chunking() {
+        let options = SqlOptions {
+            chunk_size: 2,
+            join_with_or: true,
+            ..SqlOptions::default()
+        };
+        let sql = generate_sql("1,2,3", &options).unwrap();
+        assert_eq!(sql, "(\n  id IN (1,2)\n  OR id IN (3)\n)");
+
+        let oracle = SqlOptions {
+            dialect: SqlDialect::Oracle,
+            chunk_size: 1001,
+            ..SqlOptions::default()
+        };
+        assert!(generate_sql("1", &oracle).is_err());
+    }
+
+    #[test]
+    fn test_values_and_union_all() {
+        let options = SqlOptions {
+            mode: SqlMode::Values,
+            ..SqlOptions::default()
+        };
+        let sql = generate_sql("1\n2", &options).unwrap();
+        assert_eq!(sql, "INSERT INTO tmp_ids (id) VALUES\n(1),\n(2);");
+
+        let options = SqlOptions {
+            dialect: SqlDialect::Oracle,
+            mode: SqlMode::UnionAll,
+            ..SqlOptions::default()
+        };
+        let sql = generate_sql("1\n2", &options).unwrap();
+        assert_eq!(
+            sql,
+            "WITH tmp_ids AS (\n  SELECT 1 AS id FROM DUAL\n  UNION ALL SELECT 2 FROM DUAL\n)\nSELECT id FROM tmp_ids;"
+        );
+    }
+}
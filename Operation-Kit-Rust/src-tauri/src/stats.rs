@@ -0,0 +1,136 @@
+use crate::error::{AppError, AppResult};
+use crate::text_processor::{split_items_with_lines, uses_newlines};
+use serde::Serialize;
+use std::collections::HashMap;
+
+// 报告中最多列出的重复项
+const TOP_DUPLICATES: usize = 10;
+// 报告中最多列出的非数字条目
+const MAX_LISTED_LINES: usize = 100;
+
+// 识别出的分隔符，与其他操作输出时使用的一致
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Delimiter {
+    Newline,
+    Comma,
+}
+
+// 重复出现的条目
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateItem {
+    pub value: String,
+    pub count: usize,
+}
+
+// 非数字条目及所在行
+#[derive(Debug, Clone, Serialize)]
+pub struct LineItem {
+    pub line: usize,
+    pub value: String,
+}
+
+// 列表统计报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListStats {
+    pub total: usize,
+    pub unique: usize,
+    pub duplicates: usize,
+    pub top_duplicates: Vec<DuplicateItem>,
+    pub blank_lines: usize,
+    pub min_length: usize,
+    pub max_length: usize,
+    pub non_numeric_count: usize,
+    pub non_numeric: Vec<LineItem>,
+    pub delimiter: Delimiter,
+}
+
+// 统计列表的条目数、重复、空行、长度和非数字条目；拆分方式与其他操作相同
+pub fn list_stats(input: &str) -> AppResult<ListStats> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+
+    let items = split_items_with_lines(input);
+
+    // 记录每个值的次数和首次出现的位置，重复项按次数降序、首次出现顺序排列
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (index, (_, value)) in items.iter().enumerate() {
+        counts.entry(value).or_insert((0, index)).0 += 1;
+    }
+    let mut repeated: Vec<(&str, usize, usize)> = counts
+        .iter()
+        .filter(|(_, (count, _))| *count > 1)
+        .map(|(value, (count, first))| (*value, *count, *first))
+        .collect();
+    repeated.sort_by_key(|(_, count, first)| (std::cmp::Reverse(*count), *first));
+
+    // 输入末尾的换行不算空行
+    let body = input.strip_suffix('\n').unwrap_or(input);
+    let blank_lines = body.split('\n').filter(|line| line.trim().is_empty()).count();
+
+    let lengths = items.iter().map(|(_, value)| value.chars().count());
+    let non_numeric: Vec<LineItem> = items
+        .iter()
+        .filter(|(_, value)| !value.chars().all(|c| c.is_ascii_digit()))
+        .map(|(line, value)| LineItem {
+            line: *line,
+            value: value.to_string(),
+        })
+        .collect();
+
+    Ok(ListStats {
+        total: items.len(),
+        unique: counts.len(),
+        duplicates: items.len() - counts.len(),
+        top_duplicates: repeated
+            .into_iter()
+            .take(TOP_DUPLICATES)
+            .map(|(value, count, _)| DuplicateItem {
+                value: value.to_string(),
+                count,
+            })
+            .collect(),
+        blank_lines,
+        min_length: lengths.clone().min().unwrap_or(0),
+        max_length: lengths.max().unwrap_or(0),
+        non_numeric_count: non_numeric.len(),
+        non_numeric: non_numeric.into_iter().take(MAX_LISTED_LINES).collect(),
+        delimiter: if uses_newlines(input) { Delimiter::Newline } else { Delimiter::Comma },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_stats() {
+        let stats = list_stats("123\n456\n\n123\nabc\n456\n123\n  \n7\n").unwrap();
+        assert_eq!(stats.total, 7);
+        assert_eq!(stats.unique, 4);
+        assert_eq!(stats.duplicates, 3);
+        assert_eq!(
+            stats.top_duplicates,
+            vec![
+                DuplicateItem { value: "123".to_string(), count: 3 },
+                DuplicateItem { value: "456".to_string(), count: 2 },
+            ]
+        );
+        assert_eq!(stats.blank_lines, 2);
+        assert_eq!((stats.min_length, stats.max_length), (1, 3));
+        assert_eq!(stats.non_numeric_count, 1);
+        assert_eq!(stats.non_numeric[0].line, 5);
+        assert_eq!(stats.delimiter, Delimiter::Newline);
+    }
+
+    #[test]
+    fn test_comma_delimited() {
+        let stats = list_stats("1, 2,3").unwrap();
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.duplicates, 0);
+        assert_eq!(stats.blank_lines, 0);
+        assert_eq!(stats.delimiter, Delimiter::Comma);
+    }
+}
@@ -0,0 +1,374 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use crate::oss::{validate_content, Channel, OssBackend};
+use crate::precision::repair_precision;
+use crate::s3::S3Backend;
+use crate::target::{BackendKind, OssTarget};
+use crate::vault::CredentialSecret;
+
+static CONTENTS: OnceLock<Regex> = OnceLock::new();
+
+// 对象的基本信息
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectInfo {
+    pub key: String,
+    pub size: u64,
+    // ISO 8601格式，如 2024-01-01T08:00:00.000Z
+    pub last_modified: Option<String>,
+}
+
+// 存储后端：OSS、S3兼容存储和本地文件夹都实现这组操作
+pub trait StorageBackend {
+    // 写入对象，已存在时覆盖
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()>;
+    // 读取对象内容
+    async fn get(&self, key: &str) -> Result<Vec<u8>>;
+    // 列出以prefix开头的对象，按key排序
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>>;
+    // 删除对象
+    async fn delete(&self, key: &str) -> Result<()>;
+    // 查询对象信息，不存在时返回None
+    async fn head(&self, key: &str) -> Result<Option<ObjectInfo>>;
+    // 用于提示信息的对象位置
+    fn location(&self, key: &str) -> String;
+}
+
+// 根据对象路径的扩展名推断Content-Type
+pub fn content_type(key: &str) -> &'static str {
+    match key.rsplit('.').next().map(|ext| ext.to_lowercase()).as_deref() {
+        Some("txt") => "text/plain",
+        Some("csv") => "text/csv",
+        Some("json") => "application/json",
+        Some("zip") => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+// 把HTTP头中的Last-Modified（RFC 2822）转为ISO 8601，无法解析时原样返回
+pub fn normalize_last_modified(value: &str) -> String {
+    DateTime::parse_from_rfc2822(value)
+        .map(|t| t.with_timezone(&Utc).format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn xml_field<'a>(block: &'a str, tag: &str) -> Option<&'a str> {
+    let start = block.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = block[start..].find(&format!("</{}>", tag))? + start;
+    Some(&block[start..end])
+}
+
+// 解析OSS和S3通用的ListObjects响应，返回对象列表和是否还有下一页
+pub fn parse_list_xml(xml: &str) -> Result<(Vec<ObjectInfo>, bool)> {
+    let contents = CONTENTS.get_or_init(|| Regex::new(r"(?s)<Contents>(.*?)</Contents>").expect("无效的正则表达式"));
+    let objects = contents
+        .captures_iter(xml)
+        .map(|caps| {
+            let block = caps.get(1).map_or("", |m| m.as_str());
+            let key = xml_field(block, "Key").ok_or_else(|| anyhow!("列表响应缺少Key"))?;
+            Ok(ObjectInfo {
+                key: xml_unescape(key),
+                size: xml_field(block, "Size").and_then(|s| s.trim().parse().ok()).unwrap_or(0),
+                last_modified: xml_field(block, "LastModified").map(str::to_string),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let truncated = xml_field(xml, "IsTruncated").is_some_and(|v| v.trim() == "true");
+    Ok((objects, truncated))
+}
+
+// 本地或网络共享文件夹，文件操作走tokio::fs，不阻塞异步运行时
+pub struct LocalBackend {
+    root: PathBuf,
+}
+
+impl LocalBackend {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    // 对象路径只能是相对路径，不能包含 .. 等跳出根目录的部分
+    fn resolve(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+        if key.is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(anyhow!("无效的对象路径: {}", key));
+        }
+        Ok(self.root.join(relative))
+    }
+
+    fn info(key: String, metadata: &fs::Metadata) -> ObjectInfo {
+        ObjectInfo {
+            key,
+            size: metadata.len(),
+            last_modified: metadata
+                .modified()
+                .ok()
+                .map(|t| DateTime::<Utc>::from(t).format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
+        }
+    }
+
+    // 同步遍历目录，需在spawn_blocking中调用；符号链接不跟随，避免跳出根目录或循环
+    fn walk(root: &Path, dir: &Path, prefix: &str, objects: &mut Vec<ObjectInfo>) -> Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                Self::walk(root, &path, prefix, objects)?;
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+            let key = path
+                .strip_prefix(root)?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            // 跳过写入中途留下的临时文件
+            if key.starts_with(prefix) && !key.ends_with(".uploading") {
+                objects.push(Self::info(key, &entry.metadata()?));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StorageBackend for LocalBackend {
+    // 先写临时文件再改名，避免读取方看到写了一半的文件
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        let path = self.resolve(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let temp = path.with_file_name(format!(
+            "{}.uploading",
+            path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default()
+        ));
+        tokio::fs::write(&temp, body).await?;
+        tokio::fs::rename(&temp, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.resolve(key)?;
+        tokio::fs::read(&path).await.map_err(|e| anyhow!("读取 {} 失败: {}", path.display(), e))
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        let root = self.root.clone();
+        let prefix = prefix.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut objects = Vec::new();
+            if root.is_dir() {
+                Self::walk(&root, &root, &prefix, &mut objects)?;
+            }
+            objects.sort_by(|a, b| a.key.cmp(&b.key));
+            Ok(objects)
+        })
+        .await?
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.resolve(key)?;
+        tokio::fs::remove_file(&path).await.map_err(|e| anyhow!("删除 {} 失败: {}", path.display(), e))
+    }
+
+    async fn head(&self, key: &str) -> Result<Option<ObjectInfo>> {
+        let path = self.resolve(key)?;
+        match tokio::fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_file() => Ok(Some(Self::info(key.to_string(), &metadata))),
+            _ => Ok(None),
+        }
+    }
+
+    fn location(&self, key: &str) -> String {
+        format!("本地路径: {}", self.root.join(key).display())
+    }
+}
+
+// 按目标配置选出的存储后端
+pub enum Backend {
+    Oss(OssBackend),
+    S3(S3Backend),
+    Local(LocalBackend),
+}
+
+impl Backend {
+    // OSS和S3需要凭证，本地文件夹不需要
+    pub fn open(target: &OssTarget, credential: Option<CredentialSecret>) -> Result<Self> {
+        let required = move || credential.ok_or_else(|| anyhow!("上传目标 {} 需要选择凭证", target.name));
+        Ok(match target.backend {
+            BackendKind::Oss => Backend::Oss(OssBackend::new(target.clone(), required()?)),
+            BackendKind::S3 => Backend::S3(S3Backend::new(target.clone(), required()?)),
+            BackendKind::Local => Backend::Local(LocalBackend::new(target.path.trim())),
+        })
+    }
+}
+
+impl StorageBackend for Backend {
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        match self {
+            Backend::Oss(backend) => backend.put(key, body).await,
+            Backend::S3(backend) => backend.put(key, body).await,
+            Backend::Local(backend) => backend.put(key, body).await,
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        match self {
+            Backend::Oss(backend) => backend.get(key).await,
+            Backend::S3(backend) => backend.get(key).await,
+            Backend::Local(backend) => backend.get(key).await,
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        match self {
+            Backend::Oss(backend) => backend.list(prefix).await,
+            Backend::S3(backend) => backend.list(prefix).await,
+            Backend::Local(backend) => backend.list(prefix).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match self {
+            Backend::Oss(backend) => backend.delete(key).await,
+            Backend::S3(backend) => backend.delete(key).await,
+            Backend::Local(backend) => backend.delete(key).await,
+        }
+    }
+
+    async fn head(&self, key: &str) -> Result<Option<ObjectInfo>> {
+        match self {
+            Backend::Oss(backend) => backend.head(key).await,
+            Backend::S3(backend) => backend.head(key).await,
+            Backend::Local(backend) => backend.head(key).await,
+        }
+    }
+
+    fn location(&self, key: &str) -> String {
+        match self {
+            Backend::Oss(backend) => backend.location(key),
+            Backend::S3(backend) => backend.location(key),
+            Backend::Local(backend) => backend.location(key),
+        }
+    }
+}
+
+// 上传ID列表到目标：校验内容、按模板生成对象路径，写入后核对大小
+pub async fn upload_ids(
+    backend: &impl StorageBackend,
+    target: &OssTarget,
+    content: &str,
+    channel: Channel,
+    allow_trailing_zeros: bool,
+) -> Result<String> {
+    // 展开科学计数法，拒绝无法还原的科学计数法和疑似被截断的长ID
+    let content = repair_precision(content, allow_trailing_zeros)?;
+
+    // 验证输入内容是否符合要求 (每行一个数字ID)
+    validate_content(&content)?;
+
+    let key = target.object_key(channel.as_str())?;
+    let size = content.len() as u64;
+    backend.put(&key, content.into_bytes()).await?;
+
+    match backend.head(&key).await? {
+        Some(info) if info.size == size => Ok(format!("成功上传到 {}", backend.location(&key))),
+        Some(info) => Err(anyhow!("上传后大小不一致：应为 {} 字节，实际 {} 字节", size, info.size)),
+        None => Err(anyhow!("上传后找不到对象: {}", key)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use crate::target::SignatureVersion;
+
+    #[tokio::test]
+    async fn test_local_backend_operations() {
+        let temp = TempDir::new("storage");
+        let dir = temp.path();
+        let backend = LocalBackend::new(dir);
+        backend.put("ids/vivo.txt", b"1\n2".to_vec()).await.unwrap();
+        backend.put("ids/oppo.txt", b"3".to_vec()).await.unwrap();
+        backend.put("other.txt", b"4".to_vec()).await.unwrap();
+
+        assert_eq!(backend.get("ids/vivo.txt").await.unwrap(), b"1\n2");
+        let keys: Vec<String> = backend.list("ids/").await.unwrap().into_iter().map(|o| o.key).collect();
+        assert_eq!(keys, vec!["ids/oppo.txt", "ids/vivo.txt"]);
+        assert_eq!(backend.head("ids/vivo.txt").await.unwrap().unwrap().size, 3);
+
+        backend.delete("ids/vivo.txt").await.unwrap();
+        assert!(backend.head("ids/vivo.txt").await.unwrap().is_none());
+        assert!(backend.put("../escape.txt", Vec::new()).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_local_list_skips_symlinks() {
+        let temp = TempDir::new("symlink");
+        let dir = temp.path();
+        let backend = LocalBackend::new(dir);
+        backend.put("ids/a.txt", b"1".to_vec()).await.unwrap();
+        // 指向自身所在目录的链接，跟随时会无限递归
+        std::os::unix::fs::symlink(dir, dir.join("ids/loop")).unwrap();
+        let keys: Vec<String> = backend.list("").await.unwrap().into_iter().map(|o| o.key).collect();
+        assert_eq!(keys, vec!["ids/a.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_upload_ids_to_local_target() {
+        let temp = TempDir::new("upload");
+        let dir = temp.path();
+        let target = OssTarget {
+            name: "共享目录".to_string(),
+            backend: BackendKind::Local,
+            bucket: String::new(),
+            endpoint: String::new(),
+            key_template: "tmp_{channel}_ids/{channel}.txt".to_string(),
+            signature_version: SignatureVersion::V1,
+            region: String::new(),
+            path: dir.to_string_lossy().to_string(),
+        };
+        let backend = Backend::open(&target, None).unwrap();
+        let message = upload_ids(&backend, &target, "123\n1.23E+2", Channel::Vivo, false).await.unwrap();
+        assert!(message.contains("vivo.txt"));
+        assert_eq!(backend.get("tmp_vivo_ids/vivo.txt").await.unwrap(), b"123\n123");
+        assert!(upload_ids(&backend, &target, "abc", Channel::Oppo, false).await.is_err());
+        assert!(upload_ids(&backend, &target, "1234567890123450", Channel::Oppo, false).await.is_err());
+        assert!(upload_ids(&backend, &target, "1234567890123450", Channel::Oppo, true).await.is_ok());
+    }
+
+    #[test]
+    fn test_parse_list_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult>
+  <IsTruncated>true</IsTruncated>
+  <Contents><Key>ids/a&amp;b.txt</Key><LastModified>2024-01-01T08:00:00.000Z</LastModified><Size>12</Size></Contents>
+  <Contents><Key>ids/c.txt</Key><Size>0</Size></Contents>
+</ListBucketResult>"#;
+        let (objects, truncated) = parse_list_xml(xml).unwrap();
+        assert!(truncated);
+        assert_eq!(objects[0].key, "ids/a&b.txt");
+        assert_eq!(objects[0].size, 12);
+        assert_eq!(objects[1].last_modified, None);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::error::{AppError, AppResult};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// 按名称区分的配置项，如配方、上传目标
+pub trait Named {
+    fn name(&self) -> &str;
+}
+
+// 应用数据目录下的一个JSON文件，保存一组按名称区分的配置项
+pub struct NamedStore {
+    // 文件名
+    file: &'static str,
+    // 配置项的显示名称，用于错误提示
+    kind: &'static str,
+}
+
+impl NamedStore {
+    pub const fn new(file: &'static str, kind: &'static str) -> Self {
+        Self { file, kind }
+    }
+
+    fn path(&self, dir: &Path) -> PathBuf {
+        dir.join(self.file)
+    }
+
+    fn not_found(&self, name: &str) -> AppError {
+        AppError::InvalidInput(format!("找不到{}: {}", self.kind, name))
+    }
+
+    // 读取全部配置项，文件不存在时返回空列表
+    pub fn load<T: DeserializeOwned>(&self, dir: &Path) -> AppResult<Vec<T>> {
+        let path = self.path(dir);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn write<T: Serialize>(&self, dir: &Path, items: &[T]) -> AppResult<()> {
+        fs::create_dir_all(dir)?;
+        let content = serde_json::to_string_pretty(items)?;
+        fs::write(self.path(dir), content)?;
+        Ok(())
+    }
+
+    // 根据名称查找
+    pub fn find<T: Named + DeserializeOwned>(&self, dir: &Path, name: &str) -> AppResult<T> {
+        self.load::<T>(dir)?
+            .into_iter()
+            .find(|item| item.name() == name)
+            .ok_or_else(|| self.not_found(name))
+    }
+
+    // 保存，同名的会被覆盖
+    pub fn save<T: Named + Serialize + DeserializeOwned>(&self, dir: &Path, item: T) -> AppResult<()> {
+        let mut items = self.load::<T>(dir)?;
+        match items.iter_mut().find(|existing| existing.name() == item.name()) {
+            Some(existing) => *existing = item,
+            None => items.push(item),
+        }
+        self.write(dir, &items)
+    }
+
+    // 根据名称删除
+    pub fn delete<T: Named + Serialize + DeserializeOwned>(&self, dir: &Path, name: &str) -> AppResult<()> {
+        let mut items = self.load::<T>(dir)?;
+        let before = items.len();
+        items.retain(|item| item.name() != name);
+        if items.len() == before {
+            return Err(self.not_found(name));
+        }
+        self.write(dir, &items)
+    }
+}
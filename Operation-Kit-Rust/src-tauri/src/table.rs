@@ -0,0 +1,296 @@
+use crate::crypto::{decrypt_text, encrypt_number};
+use crate::error::{AppError, AppResult};
+use crate::text_processor::QuoteOptions;
+use serde::{Deserialize, Serialize};
+
+// 表格格式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableFormat {
+    Auto,
+    Csv,
+    Tsv,
+}
+
+// 是否把第一行当作表头
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderMode {
+    Auto,
+    Yes,
+    No,
+}
+
+// 列选择：按序号（从1开始）或按表头名称
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ColumnRef {
+    Index(usize),
+    Name(String),
+}
+
+// 表格解析选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TableOptions {
+    pub format: TableFormat,
+    pub header: HeaderMode,
+    pub column: ColumnRef,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        TableOptions {
+            format: TableFormat::Auto,
+            header: HeaderMode::Auto,
+            column: ColumnRef::Index(1),
+        }
+    }
+}
+
+// 对选中列的每个单元格执行的操作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ColumnOp {
+    Encrypt,
+    Decrypt,
+    AddQuotes { options: QuoteOptions },
+    RemoveQuotes { options: QuoteOptions },
+}
+
+impl ColumnOp {
+    fn apply(&self, cell: &str) -> AppResult<String> {
+        match self {
+            ColumnOp::Encrypt => encrypt_number(cell),
+            ColumnOp::Decrypt => decrypt_text(cell),
+            ColumnOp::AddQuotes { options } => Ok(options.quote(cell)),
+            ColumnOp::RemoveQuotes { options } => Ok(options.unquote(cell)),
+        }
+    }
+}
+
+// 解析后的表格
+pub struct Table {
+    pub delimiter: u8,
+    pub header: Option<Vec<String>>,
+    pub rows: Vec<Vec<String>>,
+    // 每一行数据在原文中的起始行号；引号内有换行时一条记录会占多行
+    lines: Vec<usize>,
+}
+
+// 根据第一行是否含制表符判断CSV还是TSV
+fn detect_delimiter(input: &str, format: TableFormat) -> u8 {
+    match format {
+        TableFormat::Csv => b',',
+        TableFormat::Tsv => b'\t',
+        TableFormat::Auto => {
+            let first_line = input.lines().next().unwrap_or("");
+            if first_line.contains('\t') {
+                b'\t'
+            } else {
+                b','
+            }
+        }
+    }
+}
+
+fn is_numeric(cell: &str) -> bool {
+    let cell = cell.trim();
+    !cell.is_empty() && cell.chars().all(|c| c.is_ascii_digit())
+}
+
+// 表头判断：第一行没有纯数字单元格，而第二行在同一位置出现了纯数字
+fn detect_header(records: &[Vec<String>]) -> bool {
+    if records.len() < 2 {
+        return false;
+    }
+    let first = &records[0];
+    let second = &records[1];
+    !first.iter().any(|c| is_numeric(c)) && second.iter().take(first.len()).any(|c| is_numeric(c))
+}
+
+// 按RFC4180解析CSV/TSV，支持引号内的分隔符、换行和双引号转义
+pub fn parse_table(input: &str, format: TableFormat, header: HeaderMode) -> AppResult<Table> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+
+    let delimiter = detect_delimiter(input, format);
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(input.as_bytes());
+
+    let mut records = Vec::new();
+    let mut lines = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| match e.position() {
+            Some(position) => AppError::FormatError(format!("第 {} 行解析失败: {}", position.line(), e)),
+            None => AppError::FormatError(format!("解析失败: {}", e)),
+        })?;
+        lines.push(record.position().map_or(records.len() + 1, |p| p.line() as usize));
+        records.push(record.iter().map(str::to_string).collect::<Vec<String>>());
+    }
+
+    Ok(Table::with_lines(records, lines, delimiter, header))
+}
+
+impl Table {
+    // 由已拆分好的行构造表格，并按选项分离表头；每条记录占一行
+    pub fn from_records(records: Vec<Vec<String>>, delimiter: u8, header: HeaderMode) -> Table {
+        let lines = (1..=records.len()).collect();
+        Table::with_lines(records, lines, delimiter, header)
+    }
+
+    fn with_lines(mut records: Vec<Vec<String>>, mut lines: Vec<usize>, delimiter: u8, header: HeaderMode) -> Table {
+        let has_header = match header {
+            HeaderMode::Yes => true,
+            HeaderMode::No => false,
+            HeaderMode::Auto => detect_header(&records),
+        };
+        let header = if has_header && !records.is_empty() {
+            lines.remove(0);
+            Some(records.remove(0))
+        } else {
+            None
+        };
+
+        Table {
+            delimiter,
+            header,
+            rows: records,
+            lines,
+        }
+    }
+
+    // 将列选择解析为从0开始的列下标
+    pub fn column_index(&self, column: &ColumnRef) -> AppResult<usize> {
+        match column {
+            ColumnRef::Index(0) => Err(AppError::InvalidInput("列序号从1开始".to_string())),
+            ColumnRef::Index(i) => Ok(i - 1),
+            ColumnRef::Name(name) => self
+                .header
+                .as_ref()
+                .and_then(|h| h.iter().position(|c| c.trim() == name.trim()))
+                .ok_or_else(|| AppError::InvalidInput(format!("找不到列: {}", name))),
+        }
+    }
+
+    // 取出指定列的非空值，每行一个
+    pub fn column_values(&self, column: &ColumnRef) -> AppResult<String> {
+        let index = self.column_index(column)?;
+        let values: Vec<&str> = self
+            .rows
+            .iter()
+            .filter_map(|row| row.get(index))
+            .map(|cell| cell.trim())
+            .filter(|cell| !cell.is_empty())
+            .collect();
+        Ok(values.join("\n"))
+    }
+
+    // 重新输出为原分隔符的文本，只在需要时加引号
+    pub fn to_text(&self) -> AppResult<String> {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .flexible(true)
+            .from_writer(Vec::new());
+        for row in self.header.iter().chain(self.rows.iter()) {
+            writer
+                .write_record(row)
+                .map_err(|e| AppError::FormatError(e.to_string()))?;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| AppError::FormatError(e.to_string()))?;
+        let text = String::from_utf8(bytes)?;
+        Ok(text.trim_end_matches('\n').to_string())
+    }
+}
+
+// 提取指定列的非空值，每行一个，便于接入现有的列表处理
+pub fn extract_column(input: &str, options: &TableOptions) -> AppResult<String> {
+    let table = parse_table(input, options.format, options.header)?;
+    table.column_values(&options.column)
+}
+
+// 对指定列逐个单元格执行操作，并写回整张表；空单元格保持不变
+pub fn transform_column(input: &str, options: &TableOptions, op: &ColumnOp) -> AppResult<String> {
+    let mut table = parse_table(input, options.format, options.header)?;
+    let index = table.column_index(&options.column)?;
+
+    for (row, line) in table.rows.iter_mut().zip(&table.lines) {
+        let Some(cell) = row.get_mut(index) else {
+            continue;
+        };
+        let value = cell.trim();
+        if value.is_empty() {
+            continue;
+        }
+        *cell = op
+            .apply(value)
+            .map_err(|e| AppError::InvalidInput(format!("第 {} 行: {}", line, e)))?;
+    }
+
+    table.to_text()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::lock_config_for_test;
+
+    #[test]
+    fn test_quoted_fields_and_header() {
+        let input = "name,id\n\"Li, Lei\",1001\n\"Han \"\"Meimei\"\"\",1002";
+        let table = parse_table(input, TableFormat::Auto, HeaderMode::Auto).unwrap();
+        assert_eq!(table.header, Some(vec!["name".to_string(), "id".to_string()]));
+        assert_eq!(table.rows[0][0], "Li, Lei");
+        assert_eq!(table.rows[1][0], "Han \"Meimei\"");
+
+        let options = TableOptions {
+            column: ColumnRef::Name("id".to_string()),
+            ..TableOptions::default()
+        };
+        assert_eq!(extract_column(input, &options).unwrap(), "1001\n1002");
+    }
+
+    #[test]
+    fn test_tsv_without_header() {
+        let input = "a\t1\nb\t2";
+        let options = TableOptions {
+            column: ColumnRef::Index(2),
+            ..TableOptions::default()
+        };
+        assert_eq!(extract_column(input, &options).unwrap(), "1\n2");
+        assert!(extract_column(input, &TableOptions {
+            column: ColumnRef::Name("id".to_string()),
+            ..TableOptions::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_transform_column_writes_back() {
+        let _guard = lock_config_for_test();
+        let input = "name,id\n\"Li, Lei\",1001\nMei,1002";
+        let options = TableOptions {
+            column: ColumnRef::Name("id".to_string()),
+            ..TableOptions::default()
+        };
+        let op = ColumnOp::AddQuotes {
+            options: QuoteOptions::default(),
+        };
+        let output = transform_column(input, &options, &op).unwrap();
+        assert_eq!(output, "name,id\n\"Li, Lei\",'1001'\nMei,'1002'");
+
+        let err = transform_column("id\n12\nabc", &options, &ColumnOp::Encrypt).unwrap_err();
+        assert!(err.to_string().contains("第 3 行"));
+
+        // 引号内的换行不影响后面的行号
+        let input = "name,id\n\"Li\nLei\",12\nMei,abc";
+        let err = transform_column(input, &options, &ColumnOp::Encrypt).unwrap_err();
+        assert!(err.to_string().contains("第 4 行"), "{}", err);
+    }
+}
@@ -0,0 +1,274 @@
+use crate::crypto::get_current_config;
+use crate::error::{AppError, AppResult};
+use regex::Regex;
+use crate::store::{Named, NamedStore};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+// 上传目标保存在应用数据目录下的 oss_targets.json
+const TARGETS: NamedStore = NamedStore::new("oss_targets.json", "上传目标");
+
+static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+
+fn placeholder_regex() -> &'static Regex {
+    PLACEHOLDER.get_or_init(|| Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)\}").expect("无效的正则表达式"))
+}
+
+// 存储后端类型
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendKind {
+    // 阿里云OSS
+    #[default]
+    Oss,
+    // S3兼容存储，如 MinIO、AWS S3，使用路径风格访问
+    S3,
+    // 本地或网络共享文件夹
+    Local,
+}
+
+// 请求签名版本
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureVersion {
+    // HMAC-SHA1，Authorization为 OSS id:signature
+    #[default]
+    V1,
+    // HMAC-SHA256，签名密钥由日期、地域和产品逐级派生
+    V4,
+}
+
+// 一个命名的上传目标，可以是OSS、S3兼容存储或本地文件夹
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OssTarget {
+    pub name: String,
+    // 旧版本保存的目标没有该字段，按OSS处理
+    #[serde(default)]
+    pub backend: BackendKind,
+    #[serde(default)]
+    pub bucket: String,
+    // OSS为地域接入点，如 oss-cn-hangzhou.aliyuncs.com；S3可带协议和端口，如 http://127.0.0.1:9000
+    #[serde(default)]
+    pub endpoint: String,
+    // 对象路径模板，可用 {channel} {date} {user} {profile}
+    pub key_template: String,
+    // 旧版本保存的目标没有以下字段，按V1签名处理
+    #[serde(default)]
+    pub signature_version: SignatureVersion,
+    // 地域ID，如 cn-hangzhou，V4签名必填；S3为空时使用 us-east-1
+    #[serde(default)]
+    pub region: String,
+    // 本地文件夹路径，仅本地文件夹后端使用
+    #[serde(default)]
+    pub path: String,
+}
+
+// 渲染对象路径时可用的值
+#[derive(Debug, Clone)]
+pub struct KeyContext {
+    pub channel: String,
+    // 当天日期，yyyyMMdd
+    pub date: String,
+    // 当前系统用户名
+    pub user: String,
+    // 当前加密配置，general 或 huawei
+    pub profile: String,
+}
+
+impl KeyContext {
+    // 用当前日期、系统用户和加密配置生成上下文
+    pub fn current(channel: &str) -> Self {
+        Self {
+            channel: channel.to_string(),
+            date: chrono::Local::now().format("%Y%m%d").to_string(),
+            user: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .unwrap_or_else(|_| "unknown".to_string()),
+            profile: get_current_config().key().to_string(),
+        }
+    }
+}
+
+// 按上下文替换对象路径模板中的占位符
+pub fn render_key(template: &str, context: &KeyContext) -> AppResult<String> {
+    let mut unknown = None;
+    let key = placeholder_regex().replace_all(template, |caps: &regex::Captures| match &caps[1] {
+        "channel" => context.channel.clone(),
+        "date" => context.date.clone(),
+        "user" => context.user.clone(),
+        "profile" => context.profile.clone(),
+        _ => {
+            unknown.get_or_insert_with(|| caps[0].to_string());
+            String::new()
+        }
+    });
+    if let Some(placeholder) = unknown {
+        return Err(AppError::InvalidInput(format!(
+            "未知的占位符 {}，可用的有 {{channel}} {{date}} {{user}} {{profile}}",
+            placeholder
+        )));
+    }
+    let key = key.trim_start_matches('/').to_string();
+    if key.is_empty() {
+        return Err(AppError::InvalidInput("对象路径不能为空".to_string()));
+    }
+    Ok(key)
+}
+
+impl OssTarget {
+    fn validate_bucket(&self) -> AppResult<()> {
+        if self.bucket.trim().is_empty() || self.endpoint.trim().is_empty() {
+            return Err(AppError::InvalidInput("Bucket和Endpoint不能为空".to_string()));
+        }
+        Ok(())
+    }
+
+    // 访问地址中的主机名：bucket.endpoint
+    pub fn host(&self) -> String {
+        format!("{}.{}", self.bucket, self.endpoint)
+    }
+
+    // 为指定渠道生成对象路径
+    pub fn object_key(&self, channel: &str) -> AppResult<String> {
+        render_key(&self.key_template, &KeyContext::current(channel))
+    }
+
+    fn validate(&self) -> AppResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::InvalidInput("目标名称不能为空".to_string()));
+        }
+        match self.backend {
+            BackendKind::Oss => {
+                self.validate_bucket()?;
+                if self.endpoint.contains("://") || self.endpoint.contains('/') {
+                    return Err(AppError::InvalidInput("Endpoint只需要填写域名，如 oss-cn-hangzhou.aliyuncs.com".to_string()));
+                }
+                if self.signature_version == SignatureVersion::V4 && self.region.trim().is_empty() {
+                    return Err(AppError::InvalidInput("V4签名需要填写地域ID，如 cn-hangzhou".to_string()));
+                }
+            }
+            BackendKind::S3 => {
+                self.validate_bucket()?;
+                let host = self
+                    .endpoint
+                    .strip_prefix("http://")
+                    .or_else(|| self.endpoint.strip_prefix("https://"))
+                    .unwrap_or(&self.endpoint);
+                if host.contains("://") || host.contains('/') {
+                    return Err(AppError::InvalidInput("Endpoint只能包含协议、域名和端口，如 http://127.0.0.1:9000".to_string()));
+                }
+            }
+            BackendKind::Local => {
+                if self.path.trim().is_empty() {
+                    return Err(AppError::InvalidInput("本地文件夹路径不能为空".to_string()));
+                }
+            }
+        }
+        // 用示例值渲染一次，提前发现错误的占位符
+        let sample = KeyContext {
+            channel: "vivo".to_string(),
+            date: "20240101".to_string(),
+            user: "user".to_string(),
+            profile: "general".to_string(),
+        };
+        render_key(&self.key_template, &sample).map(|_| ())
+    }
+}
+
+impl Named for OssTarget {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+// 读取已保存的上传目标，文件不存在时返回空列表
+pub fn load_targets(dir: &Path) -> AppResult<Vec<OssTarget>> {
+    TARGETS.load(dir)
+}
+
+// 根据名称查找上传目标
+pub fn find_target(dir: &Path, name: &str) -> AppResult<OssTarget> {
+    TARGETS.find(dir, name)
+}
+
+// 保存上传目标，同名目标会被覆盖
+pub fn save_target(dir: &Path, target: OssTarget) -> AppResult<()> {
+    target.validate()?;
+    TARGETS.save(dir, target)
+}
+
+// 删除上传目标
+pub fn delete_target(dir: &Path, name: &str) -> AppResult<()> {
+    TARGETS.delete::<OssTarget>(dir, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn context() -> KeyContext {
+        KeyContext {
+            channel: "oppo".to_string(),
+            date: "20240315".to_string(),
+            user: "alice".to_string(),
+            profile: "huawei".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_key() {
+        let key = render_key("/hive2/dim/tmp_{channel}_ids/{date}/{user}_{profile}.txt", &context()).unwrap();
+        assert_eq!(key, "hive2/dim/tmp_oppo_ids/20240315/alice_huawei.txt");
+        assert!(render_key("{bucket}/{channel}.txt", &context()).is_err());
+    }
+
+    #[test]
+    fn test_save_find_delete() {
+        let temp = TempDir::new("target");
+        let dir = temp.path();
+        let target = OssTarget {
+            name: "默认".to_string(),
+            bucket: "ids".to_string(),
+            endpoint: "oss-cn-hangzhou.aliyuncs.com".to_string(),
+            key_template: "hive2/dim/tmp_{channel}_ids/{channel}.txt".to_string(),
+            backend: BackendKind::Oss,
+            signature_version: SignatureVersion::V1,
+            region: String::new(),
+            path: String::new(),
+        };
+        save_target(dir, target.clone()).unwrap();
+        assert_eq!(find_target(dir, "默认").unwrap().host(), "ids.oss-cn-hangzhou.aliyuncs.com");
+
+        let invalid = OssTarget {
+            endpoint: "https://oss-cn-hangzhou.aliyuncs.com".to_string(),
+            ..target.clone()
+        };
+        assert!(save_target(dir, invalid).is_err());
+        let missing_region = OssTarget {
+            signature_version: SignatureVersion::V4,
+            ..target.clone()
+        };
+        assert!(save_target(dir, missing_region).is_err());
+        let local = OssTarget {
+            name: "共享目录".to_string(),
+            backend: BackendKind::Local,
+            bucket: String::new(),
+            endpoint: String::new(),
+            path: "/mnt/share/ids".to_string(),
+            ..target.clone()
+        };
+        save_target(dir, local).unwrap();
+
+        // 旧版本保存的目标按OSS、V1签名处理
+        let legacy: OssTarget =
+            serde_json::from_str(r#"{"name":"旧","bucket":"ids","endpoint":"oss-cn-hangzhou.aliyuncs.com","keyTemplate":"{channel}.txt"}"#)
+                .unwrap();
+        assert_eq!((legacy.backend, legacy.signature_version), (BackendKind::Oss, SignatureVersion::V1));
+
+        delete_target(dir, "默认").unwrap();
+        assert!(find_target(dir, "默认").is_err());
+    }
+}
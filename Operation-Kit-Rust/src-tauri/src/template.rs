@@ -0,0 +1,181 @@
+use crate::crypto::{decrypt_text, encrypt_number};
+use crate::error::{AppError, AppResult};
+use crate::text_processor::split_items_with_lines;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+
+// 只有 {标识符} 形式才是占位符，JSON等模板中的其他花括号一律原样保留；
+// 需要输出字面的 {id} 时写成 {{id}}
+fn placeholder_regex() -> &'static Regex {
+    PLACEHOLDER.get_or_init(|| {
+        Regex::new(r"\{\{([A-Za-z_][A-Za-z0-9_]*)\}\}|\{([A-Za-z_][A-Za-z0-9_]*)\}").expect("无效的正则表达式")
+    })
+}
+
+// 模板中可用的占位符
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Placeholder {
+    // 原始条目
+    Id,
+    // 条目序号，从1开始
+    Index,
+    // 用当前加密配置加密后的值
+    Enc,
+    // 用当前加密配置解密后的值
+    Dec,
+    // 条目的MD5（小写十六进制）
+    Md5,
+}
+
+impl Placeholder {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "id" => Some(Placeholder::Id),
+            "index" => Some(Placeholder::Index),
+            "enc" => Some(Placeholder::Enc),
+            "dec" => Some(Placeholder::Dec),
+            "md5" => Some(Placeholder::Md5),
+            _ => None,
+        }
+    }
+}
+
+// 解析后的模板片段
+enum Segment<'a> {
+    Text(&'a str),
+    Value(Placeholder),
+}
+
+// 模板渲染选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TemplateOptions {
+    pub template: String,
+    // 渲染结果之间的分隔符，支持 \n 和 \t 转义
+    pub delimiter: String,
+}
+
+impl Default for TemplateOptions {
+    fn default() -> Self {
+        Self {
+            template: "{id}".to_string(),
+            delimiter: "\\n".to_string(),
+        }
+    }
+}
+
+fn parse_template(template: &str) -> AppResult<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+    let mut last = 0;
+    for caps in placeholder_regex().captures_iter(template) {
+        let whole = caps.get(0).expect("正则匹配必有整体");
+        segments.push(Segment::Text(&template[last..whole.start()]));
+        last = whole.end();
+        let Some(name) = caps.get(2) else {
+            // {{标识符}} 转义，去掉外层花括号
+            let escaped = whole.as_str();
+            segments.push(Segment::Text(&escaped[1..escaped.len() - 1]));
+            continue;
+        };
+        let placeholder = Placeholder::parse(name.as_str()).ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "未知的占位符 {}，可用的有 {{id}} {{index}} {{enc}} {{dec}} {{md5}}",
+                whole.as_str()
+            ))
+        })?;
+        segments.push(Segment::Value(placeholder));
+    }
+    segments.push(Segment::Text(&template[last..]));
+    Ok(segments)
+}
+
+// 把分隔符中的 \n、\t 转义为实际字符
+fn unescape_delimiter(delimiter: &str) -> String {
+    delimiter.replace("\\n", "\n").replace("\\t", "\t")
+}
+
+// 对每个条目渲染一次模板，并用指定分隔符拼接
+pub fn render_template(input: &str, options: &TemplateOptions) -> AppResult<String> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+    if options.template.is_empty() {
+        return Err(AppError::InvalidInput("模板不能为空".to_string()));
+    }
+
+    let segments = parse_template(&options.template)?;
+    let mut rendered = Vec::new();
+    for (index, (line, item)) in split_items_with_lines(input).into_iter().enumerate() {
+        let mut output = String::new();
+        for segment in &segments {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Value(placeholder) => {
+                    let value = match placeholder {
+                        Placeholder::Id => Ok(item.to_string()),
+                        Placeholder::Index => Ok((index + 1).to_string()),
+                        Placeholder::Enc => encrypt_number(item),
+                        Placeholder::Dec => decrypt_text(item),
+                        Placeholder::Md5 => Ok(format!("{:x}", md5::compute(item.as_bytes()))),
+                    }
+                    .map_err(|e| AppError::InvalidInput(format!("第 {} 行 '{}': {}", line, item, e)))?;
+                    output.push_str(&value);
+                }
+            }
+        }
+        rendered.push(output);
+    }
+
+    Ok(rendered.join(&unescape_delimiter(&options.delimiter)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::lock_config_for_test;
+
+    fn options(template: &str, delimiter: &str) -> TemplateOptions {
+        TemplateOptions {
+            template: template.to_string(),
+            delimiter: delimiter.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_template() {
+        let output = render_template("101\n102", &options("DEL user:{id}:coupon", "\\n")).unwrap();
+        assert_eq!(output, "DEL user:101:coupon\nDEL user:102:coupon");
+
+        let output = render_template("7,8", &options("{index}-{id}", ";")).unwrap();
+        assert_eq!(output, "1-7;2-8");
+    }
+
+    #[test]
+    fn test_json_template_with_encrypted_value() {
+        let _guard = lock_config_for_test();
+        let output = render_template("12345", &options(r#"{"uid": {id}, "hash": "{enc}"}"#, "\\n")).unwrap();
+        let hash = encrypt_number("12345").unwrap();
+        assert_eq!(output, format!(r#"{{"uid": 12345, "hash": "{}"}}"#, hash));
+    }
+
+    #[test]
+    fn test_template_errors() {
+        let _guard = lock_config_for_test();
+        assert!(render_template("1", &options("{uid}", "\\n")).is_err());
+        let err = render_template("1\nabc", &options("{enc}", "\\n")).unwrap_err();
+        assert!(err.to_string().contains("第 2 行"));
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        let output = render_template("7", &options("{{id}} = {id}", "\n")).unwrap();
+        assert_eq!(output, "{id} = 7");
+        // 嵌套JSON结尾的连续花括号原样保留
+        let output = render_template("7", &options(r#"{"a":{"b":{id}}}"#, "\n")).unwrap();
+        assert_eq!(output, r#"{"a":{"b":7}}"#);
+        assert_eq!(render_template("7", &options("}}{{", "\n")).unwrap(), "}}{{");
+    }
+}
@@ -1,18 +1,58 @@
 use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+// 错误信息中最多列出的行数
+const MAX_REPORTED_LINES: usize = 10;
+
+// 判断输入是否以换行为主要分隔符（换行数多于逗号数）
+pub fn uses_newlines(input: &str) -> bool {
+    input.matches('\n').count() > input.matches(',').count()
+}
+
+// 把按行发现的问题（行号、原值、说明）格式化为错误信息的正文，超出最多列出的行数时附上总数
+pub fn format_line_issues<'a>(issues: impl IntoIterator<Item = (usize, &'a str, &'a str)>) -> String {
+    let mut lines = Vec::new();
+    let mut total = 0;
+    for (line, value, message) in issues {
+        if total < MAX_REPORTED_LINES {
+            lines.push(format!("第 {} 行 '{}': {}", line, value, message));
+        }
+        total += 1;
+    }
+    if total > MAX_REPORTED_LINES {
+        lines.push(format!("…共 {} 行", total));
+    }
+    lines.join("\n")
+}
+
+// 按换行或逗号拆分输入，去除首尾空白并丢弃空项
+pub fn split_items(input: &str) -> Vec<&str> {
+    input
+        .split(['\n', ','])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+// 同split_items，但同时返回每个条目所在的行号（从1开始）
+pub fn split_items_with_lines(input: &str) -> Vec<(usize, &str)> {
+    input
+        .split('\n')
+        .enumerate()
+        .flat_map(|(i, line)| line.split(',').map(move |item| (i + 1, item.trim())))
+        .filter(|(_, item)| !item.is_empty())
+        .collect()
+}
 
 pub fn convert_format(input: &str) -> AppResult<String> {
     if input.is_empty() {
         return Err(AppError::FormatError("输入不能为空".to_string()));
     }
     
-    let uses_newlines = input.matches('\n').count() > input.matches(',').count();
-    let items: Vec<&str> = input
-        .split(|c| c == '\n' || c == ',')
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .collect();
+    let items = split_items(input);
 
-    let delimiter = if uses_newlines { "," } else { "\n" };
+    let delimiter = if uses_newlines(input) { "," } else { "\n" };
     Ok(items.join(delimiter))
 }
 
@@ -23,56 +63,208 @@ pub fn replace_chinese_commas(input: &str) -> AppResult<String> {
     Ok(input.replace('，', ","))
 }
 
-pub fn add_quotes(input: &str) -> AppResult<String> {
+// 去除重复条目，保留首次出现的顺序和原分隔符
+pub fn dedupe(input: &str) -> AppResult<String> {
     if input.is_empty() {
         return Err(AppError::FormatError("输入不能为空".to_string()));
     }
-    
-    let uses_newlines = input.matches('\n').count() > input.matches(',').count();
-    let items: Vec<&str> = input
-        .split(|c| c == '\n' || c == ',')
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .collect();
 
-    let delimiter = if uses_newlines { "\n" } else { "," };
-    let processed: Vec<String> = items
+    let mut seen = HashSet::new();
+    let items: Vec<&str> = split_items(input)
         .into_iter()
-        .map(|item| {
-            if !item.starts_with('\'') && !item.ends_with('\'') {
-                format!("'{}'", item)
-            } else {
-                item.to_string()
-            }
-        })
+        .filter(|item| seen.insert(*item))
         .collect();
 
+    let delimiter = if uses_newlines(input) { "\n" } else { "," };
+    Ok(items.join(delimiter))
+}
+
+// 引号样式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "style", rename_all = "snake_case")]
+pub enum QuoteStyle {
+    Single,   // 'x'
+    Double,   // "x"
+    Backtick, // `x`
+    // 自定义左右引号，例如 [x]、「x」
+    Custom { left: String, right: String },
+}
+
+impl QuoteStyle {
+    // 返回左右引号
+    fn pair(&self) -> (&str, &str) {
+        match self {
+            QuoteStyle::Single => ("'", "'"),
+            QuoteStyle::Double => ("\"", "\""),
+            QuoteStyle::Backtick => ("`", "`"),
+            QuoteStyle::Custom { left, right } => (left.as_str(), right.as_str()),
+        }
+    }
+}
+
+// 内嵌引号的转义方式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteEscape {
+    Doubling,  // 右引号重复一次，如 'it''s'
+    Backslash, // 引号和反斜杠前加反斜杠，如 'it\'s'
+    None,      // 不转义
+}
+
+// 添加/移除引号的选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteOptions {
+    #[serde(flatten)]
+    pub style: QuoteStyle,
+    pub escape: QuoteEscape,
+}
+
+impl Default for QuoteOptions {
+    fn default() -> Self {
+        QuoteOptions {
+            style: QuoteStyle::Single,
+            escape: QuoteEscape::Doubling,
+        }
+    }
+}
+
+impl QuoteOptions {
+    fn validate(&self) -> AppResult<()> {
+        let (left, right) = self.style.pair();
+        if left.is_empty() || right.is_empty() {
+            return Err(AppError::InvalidInput("自定义引号不能为空".to_string()));
+        }
+        Ok(())
+    }
+
+    // 判断条目是否首尾成对包裹（单独一个引号字符不算），且内部的引号都已按转义方式转义
+    fn is_quoted(&self, item: &str) -> bool {
+        let (left, right) = self.style.pair();
+        item.len() >= left.len() + right.len()
+            && item.starts_with(left)
+            && item.ends_with(right)
+            && self.is_escaped(&item[left.len()..item.len() - right.len()])
+    }
+
+    // 检查引号内的内容是否没有未转义的引号，如 'a'b' 中间的引号未转义，不算已加引号
+    fn is_escaped(&self, inner: &str) -> bool {
+        let (left, right) = self.style.pair();
+        let mut rest = inner;
+        match self.escape {
+            QuoteEscape::Doubling => {
+                while let Some(pos) = rest.find(right) {
+                    rest = &rest[pos + right.len()..];
+                    match rest.strip_prefix(right) {
+                        Some(after) => rest = after,
+                        None => return false,
+                    }
+                }
+                true
+            }
+            QuoteEscape::Backslash => {
+                while let Some(c) = rest.chars().next() {
+                    if c == '\\' {
+                        // 末尾孤立的反斜杠会转义掉右引号
+                        let mut chars = rest[1..].chars();
+                        if chars.next().is_none() {
+                            return false;
+                        }
+                        rest = chars.as_str();
+                    } else if rest.starts_with(right) || rest.starts_with(left) {
+                        return false;
+                    } else {
+                        rest = &rest[c.len_utf8()..];
+                    }
+                }
+                true
+            }
+            // 不转义时无法区分内容中的引号，只看首尾
+            QuoteEscape::None => true,
+        }
+    }
+
+    fn escape(&self, value: &str) -> String {
+        let (left, right) = self.style.pair();
+        match self.escape {
+            QuoteEscape::Doubling => value.replace(right, &right.repeat(2)),
+            QuoteEscape::Backslash => {
+                let escaped = value
+                    .replace('\\', "\\\\")
+                    .replace(right, &format!("\\{}", right));
+                if left != right {
+                    escaped.replace(left, &format!("\\{}", left))
+                } else {
+                    escaped
+                }
+            }
+            QuoteEscape::None => value.to_string(),
+        }
+    }
+
+    fn unescape(&self, value: &str) -> String {
+        let (_, right) = self.style.pair();
+        match self.escape {
+            QuoteEscape::Doubling => value.replace(&right.repeat(2), right),
+            QuoteEscape::Backslash => {
+                let mut result = String::with_capacity(value.len());
+                let mut chars = value.chars();
+                while let Some(c) = chars.next() {
+                    if c == '\\' {
+                        // 末尾孤立的反斜杠原样保留
+                        result.push(chars.next().unwrap_or('\\'));
+                    } else {
+                        result.push(c);
+                    }
+                }
+                result
+            }
+            QuoteEscape::None => value.to_string(),
+        }
+    }
+
+    // 为单个条目加引号：已成对包裹的保持不变，不成对的引号当作内容转义
+    pub fn quote(&self, item: &str) -> String {
+        if self.is_quoted(item) {
+            return item.to_string();
+        }
+        let (left, right) = self.style.pair();
+        format!("{}{}{}", left, self.escape(item), right)
+    }
+
+    // 去除单个条目的引号：只有首尾成对时才去除并还原转义，否则原样返回
+    pub fn unquote(&self, item: &str) -> String {
+        if !self.is_quoted(item) {
+            return item.to_string();
+        }
+        let (left, right) = self.style.pair();
+        self.unescape(&item[left.len()..item.len() - right.len()])
+    }
+}
+
+pub fn add_quotes(input: &str, options: &QuoteOptions) -> AppResult<String> {
+    if input.is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+    options.validate()?;
+
+    let items = split_items(input);
+
+    let delimiter = if uses_newlines(input) { "\n" } else { "," };
+    let processed: Vec<String> = items.into_iter().map(|item| options.quote(item)).collect();
+
     Ok(processed.join(delimiter))
 }
 
-pub fn remove_quotes(input: &str) -> AppResult<String> {
+pub fn remove_quotes(input: &str, options: &QuoteOptions) -> AppResult<String> {
     if input.is_empty() {
         return Err(AppError::FormatError("输入不能为空".to_string()));
     }
-    
-    let uses_newlines = input.matches('\n').count() > input.matches(',').count();
-    let items: Vec<&str> = input
-        .split(|c| c == '\n' || c == ',')
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .collect();
+    options.validate()?;
 
-    let delimiter = if uses_newlines { "\n" } else { "," };
-    let processed: Vec<String> = items
-        .into_iter()
-        .map(|item| {
-            if item.starts_with('\'') && item.ends_with('\'') {
-                item[1..item.len()-1].to_string()
-            } else {
-                item.to_string()
-            }
-        })
-        .collect();
+    let items = split_items(input);
+
+    let delimiter = if uses_newlines(input) { "\n" } else { "," };
+    let processed: Vec<String> = items.into_iter().map(|item| options.unquote(item)).collect();
 
     Ok(processed.join(delimiter))
 }
@@ -92,6 +284,22 @@ mod tests {
         assert_eq!(result, "1,2,3");
     }
 
+    #[test]
+    fn test_split_items_with_lines() {
+        let items = split_items_with_lines("1,2\n\n 3 ");
+        assert_eq!(items, vec![(1, "1"), (1, "2"), (3, "3")]);
+    }
+
+    #[test]
+    fn test_format_line_issues() {
+        let report = format_line_issues(vec![(2, "x", "无效")]);
+        assert_eq!(report, "第 2 行 'x': 无效");
+
+        let report = format_line_issues((1..=12).map(|line| (line, "x", "无效")));
+        assert_eq!(report.lines().count(), MAX_REPORTED_LINES + 1);
+        assert!(report.ends_with("\n…共 12 行"));
+    }
+
     #[test]
     fn test_replace_chinese_commas() {
         let input = "1，2，3";
@@ -99,13 +307,68 @@ mod tests {
         assert_eq!(result, "1,2,3");
     }
 
+    #[test]
+    fn test_dedupe() {
+        assert_eq!(dedupe("3,1,3,2,1").unwrap(), "3,1,2");
+        assert_eq!(dedupe("a\nb\na").unwrap(), "a\nb");
+    }
+
     #[test]
     fn test_add_remove_quotes() {
+        let options = QuoteOptions::default();
         let input = "1,2,3";
-        let with_quotes = add_quotes(input).unwrap();
+        let with_quotes = add_quotes(input, &options).unwrap();
         assert_eq!(with_quotes, "'1','2','3'");
 
-        let result = remove_quotes(&with_quotes).unwrap();
+        let result = remove_quotes(&with_quotes, &options).unwrap();
         assert_eq!(result, "1,2,3");
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_unbalanced_quotes_round_trip() {
+        let options = QuoteOptions::default();
+        let input = "'abc\nit's\n'done'";
+        let with_quotes = add_quotes(input, &options).unwrap();
+        assert_eq!(with_quotes, "'''abc'\n'it''s'\n'done'");
+
+        let result = remove_quotes(&with_quotes, &options).unwrap();
+        assert_eq!(result, "'abc\nit's\ndone");
+
+        // 不成对的引号在移除时原样保留
+        assert_eq!(remove_quotes("'abc", &options).unwrap(), "'abc");
+
+        // 中间有未转义引号的条目不算已加引号，两个方向都当作内容处理
+        let with_quotes = add_quotes("'a'b'", &options).unwrap();
+        assert_eq!(with_quotes, "'''a''b'''");
+        assert_eq!(remove_quotes(&with_quotes, &options).unwrap(), "'a'b'");
+        assert_eq!(remove_quotes("'a'b'", &options).unwrap(), "'a'b'");
+    }
+
+    #[test]
+    fn test_quote_styles() {
+        let options = QuoteOptions {
+            style: QuoteStyle::Double,
+            escape: QuoteEscape::Backslash,
+        };
+        let with_quotes = add_quotes("a\"b\nc\\d", &options).unwrap();
+        assert_eq!(with_quotes, "\"a\\\"b\"\n\"c\\\\d\"");
+        assert_eq!(remove_quotes(&with_quotes, &options).unwrap(), "a\"b\nc\\d");
+        // 反斜杠转义下，未转义的内部引号和转义了右引号的末尾反斜杠都不算已加引号
+        assert_eq!(add_quotes("\"a\"b\"", &options).unwrap(), "\"\\\"a\\\"b\\\"\"");
+        assert_eq!(add_quotes("\"a\\\"", &options).unwrap(), "\"\\\"a\\\\\\\"\"");
+
+        let options = QuoteOptions {
+            style: QuoteStyle::Custom { left: "[".to_string(), right: "]".to_string() },
+            escape: QuoteEscape::Doubling,
+        };
+        let with_quotes = add_quotes("a]b,c", &options).unwrap();
+        assert_eq!(with_quotes, "[a]]b],[c]");
+        assert_eq!(remove_quotes(&with_quotes, &options).unwrap(), "a]b,c");
+
+        let empty = QuoteOptions {
+            style: QuoteStyle::Custom { left: String::new(), right: "]".to_string() },
+            escape: QuoteEscape::None,
+        };
+        assert!(add_quotes("1", &empty).is_err());
+    }
+}
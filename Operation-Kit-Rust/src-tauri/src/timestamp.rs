@@ -0,0 +1,227 @@
+use crate::error::{AppError, AppResult};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, LocalResult, NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+// 时间戳单位
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeUnit {
+    // 按位数识别：10位以内为秒，11-13位为毫秒，14-16位为微秒；转为时间戳时输出秒
+    #[default]
+    Auto,
+    Seconds,
+    Millis,
+    Micros,
+}
+
+// 转换方向
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampDirection {
+    // 时间戳转为格式化时间
+    #[default]
+    ToDatetime,
+    // 格式化时间转为时间戳
+    ToTimestamp,
+}
+
+// 时间戳转换选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimestampOptions {
+    pub direction: TimestampDirection,
+    pub unit: TimeUnit,
+    // IANA时区名，如 Asia/Shanghai、UTC
+    pub timezone: String,
+    // chrono格式字符串，如 %Y-%m-%d %H:%M:%S
+    pub format: String,
+}
+
+impl Default for TimestampOptions {
+    fn default() -> Self {
+        Self {
+            direction: TimestampDirection::ToDatetime,
+            unit: TimeUnit::Auto,
+            timezone: "Asia/Shanghai".to_string(),
+            format: "%Y-%m-%d %H:%M:%S".to_string(),
+        }
+    }
+}
+
+// 转换失败的行
+#[derive(Debug, Clone, Serialize)]
+pub struct TimestampFailure {
+    pub line: usize,
+    pub value: String,
+    pub error: String,
+}
+
+// 转换结果；失败的行原样保留在输出中
+#[derive(Debug, Clone, Serialize)]
+pub struct TimestampReport {
+    pub output: String,
+    pub failures: Vec<TimestampFailure>,
+}
+
+pub fn parse_timezone(name: &str) -> AppResult<Tz> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| AppError::InvalidInput(format!("未知的时区: {}", name)))
+}
+
+// 按位数识别时间戳单位
+fn detect_unit(value: i64) -> AppResult<TimeUnit> {
+    match value.unsigned_abs().to_string().len() {
+        0..=10 => Ok(TimeUnit::Seconds),
+        11..=13 => Ok(TimeUnit::Millis),
+        14..=16 => Ok(TimeUnit::Micros),
+        _ => Err(AppError::InvalidInput("位数过多，无法识别时间戳单位".to_string())),
+    }
+}
+
+// 把时间戳转为UTC时间
+pub fn timestamp_to_datetime(value: i64, unit: TimeUnit) -> AppResult<DateTime<chrono::Utc>> {
+    let unit = match unit {
+        TimeUnit::Auto => detect_unit(value)?,
+        unit => unit,
+    };
+    let datetime = match unit {
+        TimeUnit::Seconds | TimeUnit::Auto => DateTime::from_timestamp(value, 0),
+        TimeUnit::Millis => DateTime::from_timestamp_millis(value),
+        TimeUnit::Micros => DateTime::from_timestamp_micros(value),
+    };
+    datetime.ok_or_else(|| AppError::InvalidInput("时间戳超出范围".to_string()))
+}
+
+fn format_timestamp(value: &str, options: &TimestampOptions, tz: Tz) -> AppResult<String> {
+    let value: i64 = value
+        .parse()
+        .map_err(|_| AppError::InvalidInput("不是有效的时间戳".to_string()))?;
+    let datetime = timestamp_to_datetime(value, options.unit)?.with_timezone(&tz);
+    // 格式中的字段无法用于该时间时，format在to_string中会panic，这里改为报错
+    let mut formatted = String::new();
+    write!(formatted, "{}", datetime.format(&options.format))
+        .map_err(|_| AppError::InvalidInput(format!("无法按格式 {} 输出时间", options.format)))?;
+    Ok(formatted)
+}
+
+// 提前检查格式字符串，避免每一行都报同样的错误
+fn validate_format(format: &str) -> AppResult<()> {
+    if format.trim().is_empty() {
+        return Err(AppError::InvalidInput("时间格式不能为空".to_string()));
+    }
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(AppError::InvalidInput(format!("无效的时间格式: {}", format)));
+    }
+    Ok(())
+}
+
+fn parse_datetime(value: &str, options: &TimestampOptions, tz: Tz) -> AppResult<i64> {
+    // 格式中不含时间时按当天0点处理
+    let naive = NaiveDateTime::parse_from_str(value, &options.format)
+        .or_else(|e| {
+            NaiveDate::parse_from_str(value, &options.format)
+                .map(|date| date.and_hms_opt(0, 0, 0).expect("0点总是有效"))
+                .map_err(|_| e)
+        })
+        .map_err(|e| AppError::FormatError(format!("无法按格式 {} 解析: {}", options.format, e)))?;
+    let datetime = match tz.from_local_datetime(&naive) {
+        LocalResult::Single(datetime) => datetime,
+        // 夏令时回拨时取较早的时间
+        LocalResult::Ambiguous(earliest, _) => earliest,
+        LocalResult::None => {
+            return Err(AppError::InvalidInput("该时间在所选时区中不存在（夏令时跳过）".to_string()))
+        }
+    };
+    Ok(match options.unit {
+        TimeUnit::Auto | TimeUnit::Seconds => datetime.timestamp(),
+        TimeUnit::Millis => datetime.timestamp_millis(),
+        TimeUnit::Micros => datetime.timestamp_micros(),
+    })
+}
+
+// 逐行转换时间戳和格式化时间，空行保留，失败的行原样保留并报告
+pub fn convert_timestamps(input: &str, options: &TimestampOptions) -> AppResult<TimestampReport> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+    validate_format(&options.format)?;
+    let tz = parse_timezone(&options.timezone)?;
+
+    let mut lines = Vec::new();
+    let mut failures = Vec::new();
+    for (i, line) in input.split('\n').enumerate() {
+        let value = line.trim();
+        if value.is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+        let result = match options.direction {
+            TimestampDirection::ToDatetime => format_timestamp(value, options, tz),
+            TimestampDirection::ToTimestamp => parse_datetime(value, options, tz).map(|ts| ts.to_string()),
+        };
+        match result {
+            Ok(converted) => lines.push(converted),
+            Err(e) => {
+                failures.push(TimestampFailure {
+                    line: i + 1,
+                    value: value.to_string(),
+                    error: e.to_string(),
+                });
+                lines.push(value.to_string());
+            }
+        }
+    }
+
+    Ok(TimestampReport {
+        output: lines.join("\n"),
+        failures,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_detect_units() {
+        let report = convert_timestamps("1700000000\n1700000000123\n1700000000123456", &TimestampOptions::default()).unwrap();
+        assert_eq!(report.output, "2023-11-15 06:13:20\n2023-11-15 06:13:20\n2023-11-15 06:13:20");
+        assert!(report.failures.is_empty());
+    }
+
+    #[test]
+    fn test_to_timestamp_with_timezone_and_format() {
+        let options = TimestampOptions {
+            direction: TimestampDirection::ToTimestamp,
+            unit: TimeUnit::Millis,
+            timezone: "UTC".to_string(),
+            format: "%Y/%m/%d".to_string(),
+        };
+        let report = convert_timestamps("2023/11/15\nyesterday", &options).unwrap();
+        assert_eq!(report.output, "1700006400000\nyesterday");
+        assert_eq!(report.failures[0].line, 2);
+
+        let bad_tz = TimestampOptions {
+            timezone: "Mars/Base".to_string(),
+            ..TimestampOptions::default()
+        };
+        assert!(convert_timestamps("1", &bad_tz).is_err());
+    }
+
+    #[test]
+    fn test_invalid_format_is_rejected() {
+        let options = TimestampOptions {
+            format: "%Y-%Q".to_string(),
+            ..TimestampOptions::default()
+        };
+        let err = convert_timestamps("1700000000", &options).unwrap_err();
+        assert!(err.to_string().contains("%Y-%Q"));
+        // 直接调用时也不会panic
+        let tz = parse_timezone("UTC").unwrap();
+        assert!(format_timestamp("1700000000", &options, tz).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use crate::error::{AppError, AppResult};
+use crate::text_processor::{split_items_with_lines, uses_newlines};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+// 身份证前17位的加权因子和校验码
+const ID_CARD_WEIGHTS: [u32; 17] = [7, 9, 10, 5, 8, 4, 2, 1, 6, 3, 7, 9, 10, 5, 8, 4, 2];
+const ID_CARD_CHECK_CODES: [char; 11] = ['1', '0', 'X', '9', '8', '7', '6', '5', '4', '3', '2'];
+
+// 可选的校验规则
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum Validator {
+    // 18位居民身份证，含出生日期和校验码
+    IdCard,
+    // 15位IMEI（Luhn校验）或14/15位十六进制MEID
+    Imei,
+    // 大陆手机号，按号段识别运营商
+    Mobile,
+    // OAID：UUID格式或16/64位十六进制，全0无效
+    Oaid,
+    // IDFA/UUID：8-4-4-4-12格式，全0无效
+    Idfa,
+    // 指定长度范围的纯数字ID
+    NumericId { min_length: usize, max_length: usize },
+}
+
+impl Validator {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Validator::IdCard => "身份证号",
+            Validator::Imei => "IMEI/MEID",
+            Validator::Mobile => "手机号",
+            Validator::Oaid => "OAID",
+            Validator::Idfa => "IDFA",
+            Validator::NumericId { .. } => "数字ID",
+        }
+    }
+
+    // 校验单个值；通过时返回说明（如运营商），不通过时返回原因
+    pub fn check(&self, value: &str) -> Result<String, String> {
+        match self {
+            Validator::IdCard => check_id_card(value),
+            Validator::Imei => check_imei(value),
+            Validator::Mobile => mobile_carrier(value)
+                .map(str::to_string)
+                .ok_or_else(|| "不是有效的大陆手机号".to_string()),
+            Validator::Oaid => check_oaid(value),
+            Validator::Idfa => check_uuid(value).map(|_| "IDFA".to_string()),
+            Validator::NumericId { min_length, max_length } => {
+                if !value.chars().all(|c| c.is_ascii_digit()) {
+                    Err("包含非数字字符".to_string())
+                } else if !(*min_length..=*max_length).contains(&value.len()) {
+                    Err(format!("长度为{}，要求{}到{}位", value.len(), min_length, max_length))
+                } else {
+                    Ok(format!("{}位数字", value.len()))
+                }
+            }
+        }
+    }
+}
+
+fn check_id_card(value: &str) -> Result<String, String> {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() != 18 {
+        return Err("长度必须为18位".to_string());
+    }
+    if !chars[..17].iter().all(|c| c.is_ascii_digit()) {
+        return Err("前17位必须为数字".to_string());
+    }
+
+    let birth = &value[6..14];
+    if NaiveDate::parse_from_str(birth, "%Y%m%d").is_err() {
+        return Err(format!("出生日期 {} 无效", birth));
+    }
+
+    let sum: u32 = chars[..17]
+        .iter()
+        .zip(ID_CARD_WEIGHTS.iter())
+        .map(|(c, w)| c.to_digit(10).unwrap_or(0) * w)
+        .sum();
+    let expected = ID_CARD_CHECK_CODES[(sum % 11) as usize];
+    if chars[17].to_ascii_uppercase() != expected {
+        return Err(format!("校验码错误，应为{}", expected));
+    }
+    Ok(format!("出生日期 {}", birth))
+}
+
+// Luhn校验：从右往左，偶数位乘2后各位相加
+fn luhn_valid(value: &str, radix: u32) -> bool {
+    let mut sum = 0;
+    for (i, c) in value.chars().rev().enumerate() {
+        let Some(mut digit) = c.to_digit(radix) else {
+            return false;
+        };
+        if i % 2 == 1 {
+            digit *= 2;
+            digit = digit / radix + digit % radix;
+        }
+        sum += digit;
+    }
+    sum % radix == 0
+}
+
+fn check_imei(value: &str) -> Result<String, String> {
+    let is_digits = value.chars().all(|c| c.is_ascii_digit());
+    let is_hex = value.chars().all(|c| c.is_ascii_hexdigit());
+    match value.len() {
+        15 if is_digits => {
+            if luhn_valid(value, 10) {
+                Ok("IMEI".to_string())
+            } else {
+                Err("IMEI的Luhn校验位错误".to_string())
+            }
+        }
+        14 if is_hex => Ok("MEID".to_string()),
+        15 if is_hex => {
+            if luhn_valid(value, 16) {
+                Ok("MEID".to_string())
+            } else {
+                Err("MEID的校验位错误".to_string())
+            }
+        }
+        _ => Err("IMEI应为15位数字，MEID应为14位十六进制".to_string()),
+    }
+}
+
+// 根据号段返回运营商，不是有效手机号时返回None
+pub fn mobile_carrier(value: &str) -> Option<&'static str> {
+    if value.len() != 11 || !value.starts_with('1') || !value.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let carrier = match &value[..3] {
+        "134" | "135" | "136" | "137" | "138" | "139" | "147" | "148" | "150" | "151" | "152"
+        | "157" | "158" | "159" | "172" | "178" | "182" | "183" | "184" | "187" | "188" | "195"
+        | "197" | "198" => "中国移动",
+        "130" | "131" | "132" | "145" | "146" | "155" | "156" | "166" | "175" | "176" | "185"
+        | "186" | "196" => "中国联通",
+        "133" | "149" | "153" | "173" | "174" | "177" | "180" | "181" | "189" | "190" | "191"
+        | "193" | "199" => "中国电信",
+        "192" => "中国广电",
+        "162" | "165" | "167" | "170" | "171" => "虚拟运营商",
+        _ => return None,
+    };
+    Some(carrier)
+}
+
+fn check_uuid(value: &str) -> Result<(), String> {
+    let groups: Vec<&str> = value.split('-').collect();
+    let lengths: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+    if lengths != [8, 4, 4, 4, 12] || !groups.iter().all(|g| g.chars().all(|c| c.is_ascii_hexdigit())) {
+        return Err("格式应为8-4-4-4-12位十六进制".to_string());
+    }
+    if value.chars().all(|c| c == '0' || c == '-') {
+        return Err("全0值表示用户关闭了广告追踪".to_string());
+    }
+    Ok(())
+}
+
+fn check_oaid(value: &str) -> Result<String, String> {
+    if value.contains('-') {
+        return check_uuid(value).map(|_| "UUID格式".to_string());
+    }
+    if !matches!(value.len(), 16 | 64) || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("应为UUID格式或16/64位十六进制".to_string());
+    }
+    if value.chars().all(|c| c == '0') {
+        return Err("全0值表示用户关闭了广告追踪".to_string());
+    }
+    Ok(format!("{}位十六进制", value.len()))
+}
+
+// 单行校验结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationLine {
+    pub line: usize,
+    pub value: String,
+    pub valid: bool,
+    pub detail: String,
+}
+
+// 校验报告：逐行结果和只保留有效值的输出
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub output: String,
+    pub valid: usize,
+    pub invalid: usize,
+    pub lines: Vec<ValidationLine>,
+}
+
+// 逐行校验输入，输出保留原分隔符
+pub fn validate_list(input: &str, validator: &Validator) -> AppResult<ValidationReport> {
+    if input.trim().is_empty() {
+        return Err(AppError::FormatError("输入不能为空".to_string()));
+    }
+
+    let mut valid_items = Vec::new();
+    let mut lines = Vec::new();
+    for (line, value) in split_items_with_lines(input) {
+        let result = validator.check(value);
+        if result.is_ok() {
+            valid_items.push(value);
+        }
+        let valid = result.is_ok();
+        lines.push(ValidationLine {
+            line,
+            value: value.to_string(),
+            valid,
+            detail: result.unwrap_or_else(|reason| reason),
+        });
+    }
+
+    let delimiter = if uses_newlines(input) { "\n" } else { "," };
+    Ok(ValidationReport {
+        output: valid_items.join(delimiter),
+        valid: valid_items.len(),
+        invalid: lines.len() - valid_items.len(),
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_card() {
+        assert!(Validator::IdCard.check("11010519491231002X").is_ok());
+        assert!(Validator::IdCard.check("11010519491231002x").is_ok());
+        assert!(Validator::IdCard.check("110105194912310021").is_err());
+        assert!(Validator::IdCard.check("110105194913310029").is_err());
+    }
+
+    #[test]
+    fn test_imei_and_meid() {
+        assert_eq!(Validator::Imei.check("490154203237518"), Ok("IMEI".to_string()));
+        assert!(Validator::Imei.check("490154203237519").is_err());
+        assert_eq!(Validator::Imei.check("A0000000002329"), Ok("MEID".to_string()));
+    }
+
+    #[test]
+    fn test_mobile_and_ad_ids() {
+        assert_eq!(Validator::Mobile.check("13800138000"), Ok("中国移动".to_string()));
+        assert!(Validator::Mobile.check("12800138000").is_err());
+        assert!(Validator::Idfa.check("00000000-0000-0000-0000-000000000000").is_err());
+        assert!(Validator::Oaid.check("0123456789abcdef").is_ok());
+        assert!(Validator::Oaid.check("xyz").is_err());
+    }
+
+    #[test]
+    fn test_validate_list() {
+        let validator = Validator::NumericId { min_length: 3, max_length: 5 };
+        let report = validate_list("123\nab\n12\n45678", &validator).unwrap();
+        assert_eq!(report.output, "123\n45678");
+        assert_eq!((report.valid, report.invalid), (2, 2));
+        assert_eq!(report.lines[1].line, 2);
+        assert!(!report.lines[1].valid);
+    }
+}
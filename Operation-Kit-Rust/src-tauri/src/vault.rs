@@ -0,0 +1,498 @@
+use crate::error::{AppError, AppResult};
+use crate::mask::{mask_value, MaskOptions, MaskRule};
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// 保险库文件名（位于应用数据目录下）
+const VAULT_FILE: &str = "credentials.vault";
+const VAULT_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const MIN_PASSWORD_LEN: usize = 8;
+// STS凭证在过期前多少分钟开始提醒
+const EXPIRY_WARNING_MINUTES: i64 = 10;
+
+// 当前会话中已解锁的保险库，锁定或退出应用后需要重新输入主密码
+static SESSION: Mutex<Option<Vault>> = Mutex::new(None);
+
+// 凭证的密钥部分；Debug输出会隐藏密钥，避免出现在日志中
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum CredentialSecret {
+    // 长期有效的AccessKey
+    AccessKey { access_id: String, access_key: String },
+    // STS临时凭证，过期时间为RFC3339格式，如 2024-01-01T08:00:00Z
+    Sts {
+        access_id: String,
+        access_key: String,
+        security_token: String,
+        expiration: String,
+    },
+}
+
+impl CredentialSecret {
+    pub fn access_id(&self) -> &str {
+        match self {
+            CredentialSecret::AccessKey { access_id, .. } | CredentialSecret::Sts { access_id, .. } => access_id,
+        }
+    }
+
+    pub fn access_key(&self) -> &str {
+        match self {
+            CredentialSecret::AccessKey { access_key, .. } | CredentialSecret::Sts { access_key, .. } => access_key,
+        }
+    }
+
+    // 只有STS凭证需要随请求发送x-oss-security-token
+    pub fn security_token(&self) -> Option<&str> {
+        match self {
+            CredentialSecret::AccessKey { .. } => None,
+            CredentialSecret::Sts { security_token, .. } => Some(security_token),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            CredentialSecret::AccessKey { .. } => "access_key",
+            CredentialSecret::Sts { .. } => "sts",
+        }
+    }
+
+    fn expiration(&self) -> AppResult<Option<DateTime<Utc>>> {
+        match self {
+            CredentialSecret::AccessKey { .. } => Ok(None),
+            CredentialSecret::Sts { expiration, .. } => DateTime::parse_from_rfc3339(expiration.trim())
+                .map(|t| Some(t.with_timezone(&Utc)))
+                .map_err(|_| AppError::InvalidInput(format!("无效的过期时间: {}，应为 2024-01-01T08:00:00Z 格式", expiration))),
+        }
+    }
+
+    // 检查STS凭证是否可用：已过期时报错，即将过期时返回提醒
+    pub fn check_expiry(&self, now: DateTime<Utc>) -> AppResult<Option<String>> {
+        let Some(expiration) = self.expiration()? else {
+            return Ok(None);
+        };
+        if now >= expiration {
+            return Err(AppError::InvalidInput(format!(
+                "STS凭证已于 {} 过期，请更新凭证",
+                expiration.format("%Y-%m-%d %H:%M:%S UTC")
+            )));
+        }
+        let minutes = (expiration - now).num_minutes();
+        if minutes < EXPIRY_WARNING_MINUTES {
+            return Ok(Some(format!("STS凭证将在{}分钟内过期，请及时更新", minutes + 1)));
+        }
+        Ok(None)
+    }
+}
+
+impl fmt::Debug for CredentialSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CredentialSecret::AccessKey { access_id, .. } => f
+                .debug_struct("AccessKey")
+                .field("access_id", &mask_access_id(access_id))
+                .field("access_key", &"***")
+                .finish(),
+            CredentialSecret::Sts { access_id, expiration, .. } => f
+                .debug_struct("Sts")
+                .field("access_id", &mask_access_id(access_id))
+                .field("access_key", &"***")
+                .field("security_token", &"***")
+                .field("expiration", expiration)
+                .finish(),
+        }
+    }
+}
+
+// 一个命名的凭证
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Credential {
+    pub name: String,
+    #[serde(flatten)]
+    pub secret: CredentialSecret,
+}
+
+// 返回给前端的凭证摘要，不包含密钥
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialSummary {
+    pub name: String,
+    pub kind: String,
+    pub access_id: String,
+    // 仅STS凭证有过期时间
+    pub expiration: Option<String>,
+}
+
+// 保险库状态
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultStatus {
+    pub exists: bool,
+    pub unlocked: bool,
+}
+
+// 派生密钥用的Argon2参数，随保险库文件保存；不依赖argon2库的默认值，库升级后旧保险库仍能打开
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KdfParams {
+    algorithm: String,
+    version: u32,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+// 新建保险库使用的参数，即argon2 0.5的默认值；没有保存参数的旧保险库也是用它创建的
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            algorithm: "argon2id".to_string(),
+            version: 0x13,
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+impl KdfParams {
+    fn argon2(&self) -> AppResult<Argon2<'static>> {
+        let unsupported = || AppError::FormatError("保险库使用了不支持的密钥派生参数".to_string());
+        let algorithm = match self.algorithm.as_str() {
+            "argon2id" => Algorithm::Argon2id,
+            "argon2i" => Algorithm::Argon2i,
+            "argon2d" => Algorithm::Argon2d,
+            _ => return Err(unsupported()),
+        };
+        let version = Version::try_from(self.version).map_err(|_| unsupported())?;
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(|_| unsupported())?;
+        Ok(Argon2::new(algorithm, version, params))
+    }
+}
+
+// 保险库文件内容：凭证列表序列化后用AES-256-GCM加密，密钥由主密码经Argon2id派生
+#[derive(Debug, Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    #[serde(default)]
+    kdf: KdfParams,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn mask_access_id(access_id: &str) -> String {
+    let options = MaskOptions {
+        rule: MaskRule::Generic,
+        keep_first: 4,
+        keep_last: 2,
+        ..MaskOptions::default()
+    };
+    mask_value(access_id, &options)
+}
+
+fn vault_path(dir: &Path) -> PathBuf {
+    dir.join(VAULT_FILE)
+}
+
+fn decode_field(value: &str, expected_len: Option<usize>) -> AppResult<Vec<u8>> {
+    let bytes = BASE64
+        .decode(value)
+        .map_err(|_| AppError::FormatError("保险库文件已损坏".to_string()))?;
+    if expected_len.is_some_and(|len| len != bytes.len()) {
+        return Err(AppError::FormatError("保险库文件已损坏".to_string()));
+    }
+    Ok(bytes)
+}
+
+fn read_vault_file(dir: &Path) -> AppResult<Option<VaultFile>> {
+    let path = vault_path(dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let file: VaultFile = serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|_| AppError::FormatError("保险库文件已损坏".to_string()))?;
+    if file.version != VAULT_VERSION {
+        return Err(AppError::FormatError(format!("不支持的保险库版本: {}", file.version)));
+    }
+    Ok(Some(file))
+}
+
+// 由主密码派生的密钥；只保存在内存中
+#[derive(Clone)]
+struct Vault {
+    kdf: KdfParams,
+    salt: Vec<u8>,
+    key: [u8; 32],
+}
+
+impl Vault {
+    fn derive(password: &str, kdf: KdfParams, salt: Vec<u8>) -> AppResult<Self> {
+        let mut key = [0u8; 32];
+        kdf.argon2()?
+            .hash_password_into(password.as_bytes(), &salt, &mut key)
+            .map_err(|e| AppError::EncryptError(format!("派生密钥失败: {}", e)))?;
+        Ok(Self { kdf, salt, key })
+    }
+
+    // 用新的随机盐创建保险库
+    fn create(password: &str) -> AppResult<Self> {
+        if password.chars().count() < MIN_PASSWORD_LEN {
+            return Err(AppError::InvalidInput(format!("主密码至少需要{}个字符", MIN_PASSWORD_LEN)));
+        }
+        let mut salt = vec![0u8; SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self::derive(password, KdfParams::default(), salt)
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key))
+    }
+
+    fn decrypt(&self, file: &VaultFile) -> AppResult<Vec<Credential>> {
+        let nonce = decode_field(&file.nonce, Some(NONCE_LEN))?;
+        let ciphertext = decode_field(&file.ciphertext, None)?;
+        // 认证失败时不区分密码错误和文件被篡改，也不带出任何内容
+        let plaintext = self
+            .cipher()
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| AppError::DecryptError("主密码错误或保险库已损坏".to_string()))?;
+        serde_json::from_slice(&plaintext).map_err(|_| AppError::FormatError("保险库内容已损坏".to_string()))
+    }
+
+    fn load(&self, dir: &Path) -> AppResult<Vec<Credential>> {
+        match read_vault_file(dir)? {
+            Some(file) => self.decrypt(&file),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    // 每次写入都使用新的随机nonce
+    fn store(&self, dir: &Path, credentials: &[Credential]) -> AppResult<()> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let plaintext = serde_json::to_vec(credentials)?;
+        let ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_ref())
+            .map_err(|_| AppError::EncryptError("加密保险库失败".to_string()))?;
+        let file = VaultFile {
+            version: VAULT_VERSION,
+            kdf: self.kdf.clone(),
+            salt: BASE64.encode(&self.salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        fs::create_dir_all(dir)?;
+        fs::write(vault_path(dir), serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    // 打开已有保险库，或在不存在时用该密码新建
+    fn open_or_create(dir: &Path, password: &str) -> AppResult<Self> {
+        match read_vault_file(dir)? {
+            Some(file) => {
+                let vault = Self::derive(password, file.kdf.clone(), decode_field(&file.salt, Some(SALT_LEN))?)?;
+                vault.decrypt(&file)?;
+                Ok(vault)
+            }
+            None => {
+                let vault = Self::create(password)?;
+                vault.store(dir, &[])?;
+                Ok(vault)
+            }
+        }
+    }
+}
+
+fn unlocked() -> AppResult<Vault> {
+    SESSION
+        .lock()
+        .map_err(|_| AppError::InvalidInput("保险库状态异常".to_string()))?
+        .clone()
+        .ok_or_else(|| AppError::InvalidInput("保险库未解锁，请先输入主密码".to_string()))
+}
+
+// 查询保险库是否存在、是否已解锁
+pub fn vault_status(dir: &Path) -> VaultStatus {
+    VaultStatus {
+        exists: vault_path(dir).exists(),
+        unlocked: SESSION.lock().map(|s| s.is_some()).unwrap_or(false),
+    }
+}
+
+// 用主密码解锁保险库；第一次使用时会以该密码新建
+pub fn unlock_vault(dir: &Path, password: &str) -> AppResult<()> {
+    let vault = Vault::open_or_create(dir, password)?;
+    *SESSION
+        .lock()
+        .map_err(|_| AppError::InvalidInput("保险库状态异常".to_string()))? = Some(vault);
+    Ok(())
+}
+
+// 锁定保险库，清除内存中的密钥
+pub fn lock_vault() {
+    if let Ok(mut session) = SESSION.lock() {
+        *session = None;
+    }
+}
+
+// 列出凭证名称和脱敏后的AccessKey ID
+pub fn list_credentials(dir: &Path) -> AppResult<Vec<CredentialSummary>> {
+    Ok(unlocked()?
+        .load(dir)?
+        .into_iter()
+        .map(|c| CredentialSummary {
+            kind: c.secret.kind().to_string(),
+            access_id: mask_access_id(c.secret.access_id()),
+            expiration: match &c.secret {
+                CredentialSecret::AccessKey { .. } => None,
+                CredentialSecret::Sts { expiration, .. } => Some(expiration.clone()),
+            },
+            name: c.name,
+        })
+        .collect())
+}
+
+// 按名称取出凭证，用于签名请求
+pub fn find_credential(dir: &Path, name: &str) -> AppResult<Credential> {
+    unlocked()?
+        .load(dir)?
+        .into_iter()
+        .find(|c| c.name == name)
+        .ok_or_else(|| AppError::InvalidInput(format!("找不到凭证: {}", name)))
+}
+
+fn validate_credential(credential: &Credential) -> AppResult<()> {
+    if credential.name.trim().is_empty() {
+        return Err(AppError::InvalidInput("凭证名称不能为空".to_string()));
+    }
+    let secret = &credential.secret;
+    if secret.access_id().trim().is_empty() || secret.access_key().trim().is_empty() {
+        return Err(AppError::InvalidInput("Access ID和Access Key不能为空".to_string()));
+    }
+    if secret.security_token().is_some_and(|token| token.trim().is_empty()) {
+        return Err(AppError::InvalidInput("STS凭证的Security Token不能为空".to_string()));
+    }
+    secret.expiration()?;
+    Ok(())
+}
+
+// 保存凭证，同名凭证会被覆盖
+pub fn save_credential(dir: &Path, credential: Credential) -> AppResult<()> {
+    validate_credential(&credential)?;
+    let vault = unlocked()?;
+    let mut credentials = vault.load(dir)?;
+    match credentials.iter_mut().find(|c| c.name == credential.name) {
+        Some(existing) => *existing = credential,
+        None => credentials.push(credential),
+    }
+    vault.store(dir, &credentials)
+}
+
+// 删除凭证
+pub fn delete_credential(dir: &Path, name: &str) -> AppResult<()> {
+    let vault = unlocked()?;
+    let mut credentials = vault.load(dir)?;
+    let before = credentials.len();
+    credentials.retain(|c| c.name != name);
+    if credentials.len() == before {
+        return Err(AppError::InvalidInput(format!("找不到凭证: {}", name)));
+    }
+    vault.store(dir, &credentials)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn credential() -> Credential {
+        Credential {
+            name: "生产".to_string(),
+            secret: CredentialSecret::AccessKey {
+                access_id: "LTAI5tExampleId".to_string(),
+                access_key: "SuperSecretKeyValue".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_vault_round_trip_and_wrong_password() {
+        let temp = TempDir::new("vault");
+        let dir = temp.path();
+        let vault = Vault::open_or_create(dir, "correct horse").unwrap();
+        vault.store(dir, &[credential()]).unwrap();
+
+        // 文件中不出现明文密钥
+        let raw = fs::read_to_string(vault_path(dir)).unwrap();
+        assert!(!raw.contains("SuperSecretKeyValue") && !raw.contains("LTAI5tExampleId"));
+
+        let reopened = Vault::open_or_create(dir, "correct horse").unwrap();
+        assert_eq!(reopened.load(dir).unwrap(), vec![credential()]);
+
+        let err = Vault::open_or_create(dir, "wrong password").err().unwrap().to_string();
+        assert!(err.contains("主密码错误"));
+    }
+
+    #[test]
+    fn test_vault_uses_saved_kdf_params() {
+        let temp = TempDir::new("vault-kdf");
+        let dir = temp.path();
+        let vault = Vault::open_or_create(dir, "correct horse").unwrap();
+        vault.store(dir, &[credential()]).unwrap();
+        let mut file = read_vault_file(dir).unwrap().unwrap();
+        assert_eq!(file.kdf, KdfParams::default());
+
+        // 没有kdf字段的旧文件按创建时的参数派生
+        let raw = fs::read_to_string(vault_path(dir)).unwrap();
+        let mut legacy: serde_json::Value = serde_json::from_str(&raw).unwrap();
+        legacy.as_object_mut().unwrap().remove("kdf");
+        fs::write(vault_path(dir), legacy.to_string()).unwrap();
+        let reopened = Vault::open_or_create(dir, "correct horse").unwrap();
+        assert_eq!(reopened.load(dir).unwrap(), vec![credential()]);
+
+        // 用非默认参数保存的保险库必须按保存的参数派生
+        file.kdf.iterations = 1;
+        let custom = Vault::derive("correct horse", file.kdf.clone(), vault.salt.clone()).unwrap();
+        custom.store(dir, &[credential()]).unwrap();
+        let reopened = Vault::open_or_create(dir, "correct horse").unwrap();
+        assert_eq!(reopened.kdf.iterations, 1);
+        assert_eq!(reopened.load(dir).unwrap(), vec![credential()]);
+    }
+
+    #[test]
+    fn test_secrets_hidden_from_debug() {
+        let debug = format!("{:?}", credential());
+        assert!(!debug.contains("SuperSecretKeyValue"));
+        assert!(debug.contains("LTAI*********"));
+        assert!(Vault::create("short").is_err());
+    }
+
+    #[test]
+    fn test_sts_expiry() {
+        let secret = CredentialSecret::Sts {
+            access_id: "STS.NTExampleId".to_string(),
+            access_key: "TemporarySecret".to_string(),
+            security_token: "CAISExampleToken".to_string(),
+            expiration: "2024-01-01T08:00:00Z".to_string(),
+        };
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        assert_eq!(secret.check_expiry(at("2024-01-01T07:00:00Z")).unwrap(), None);
+        assert!(secret.check_expiry(at("2024-01-01T07:55:30Z")).unwrap().unwrap().contains("5分钟"));
+        assert!(secret.check_expiry(at("2024-01-01T08:00:00Z")).is_err());
+
+        let debug = format!("{:?}", secret);
+        assert!(!debug.contains("TemporarySecret") && !debug.contains("CAISExampleToken"));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::error::{AppError, AppResult};
+use crate::table::{ColumnRef, HeaderMode, Table};
+use calamine::{open_workbook_auto, Data, Reader};
+use rust_xlsxwriter::{Format, Workbook};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// 导出时每列的默认宽度
+const COLUMN_WIDTH: f64 = 24.0;
+
+// xlsx导入选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct XlsxImportOptions {
+    // 工作表名称，为空时使用第一个工作表
+    pub sheet: Option<String>,
+    pub header: HeaderMode,
+    pub column: ColumnRef,
+}
+
+impl Default for XlsxImportOptions {
+    fn default() -> Self {
+        XlsxImportOptions {
+            sheet: None,
+            header: HeaderMode::Auto,
+            column: ColumnRef::Index(1),
+        }
+    }
+}
+
+// 导出的一列：表头和每行一个值的内容；空行保留为空单元格，使各列按行对齐
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XlsxColumn {
+    pub header: String,
+    pub content: String,
+}
+
+fn xlsx_error(err: impl std::fmt::Display) -> AppError {
+    AppError::FormatError(format!("Excel文件处理失败: {}", err))
+}
+
+// 单元格转文本；浮点数按完整的十进制输出，不会出现科学计数法
+fn cell_to_string(cell: &Data) -> String {
+    cell.to_string().trim().to_string()
+}
+
+// 列出工作簿中的所有工作表名称
+pub fn list_sheets(path: &Path) -> AppResult<Vec<String>> {
+    let workbook = open_workbook_auto(path).map_err(xlsx_error)?;
+    Ok(workbook.sheet_names())
+}
+
+// 读取指定工作表的指定列，返回每行一个值的列表
+pub fn read_column(path: &Path, options: &XlsxImportOptions) -> AppResult<String> {
+    let mut workbook = open_workbook_auto(path).map_err(xlsx_error)?;
+    let sheet = match &options.sheet {
+        Some(name) if !name.trim().is_empty() => name.clone(),
+        _ => workbook
+            .sheet_names()
+            .first()
+            .cloned()
+            .ok_or_else(|| AppError::FormatError("Excel文件中没有工作表".to_string()))?,
+    };
+    let range = workbook.worksheet_range(&sheet).map_err(xlsx_error)?;
+
+    let records: Vec<Vec<String>> = range
+        .rows()
+        .map(|row| row.iter().map(cell_to_string).collect())
+        .collect();
+    if records.is_empty() {
+        return Err(AppError::FormatError(format!("工作表 '{}' 为空", sheet)));
+    }
+
+    Table::from_records(records, b',', options.header).column_values(&options.column)
+}
+
+// 把多列内容写入新的xlsx文件；所有单元格都以文本格式写入，长ID不会变成科学计数法
+pub fn write_columns(path: &Path, sheet: &str, columns: &[XlsxColumn]) -> AppResult<()> {
+    if columns.is_empty() {
+        return Err(AppError::InvalidInput("至少需要导出一列".to_string()));
+    }
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    if !sheet.trim().is_empty() {
+        worksheet.set_name(sheet.trim()).map_err(xlsx_error)?;
+    }
+
+    let text_format = Format::new().set_num_format("@");
+    let header_format = Format::new().set_bold().set_num_format("@");
+    for (col, column) in columns.iter().enumerate() {
+        let col = col as u16;
+        worksheet
+            .set_column_format(col, &text_format)
+            .map_err(xlsx_error)?;
+        worksheet
+            .set_column_width(col, COLUMN_WIDTH)
+            .map_err(xlsx_error)?;
+        worksheet
+            .write_string_with_format(0, col, &column.header, &header_format)
+            .map_err(xlsx_error)?;
+        for (row, value) in column.content.lines().enumerate() {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            worksheet
+                .write_string_with_format(row as u32 + 1, col, value, &text_format)
+                .map_err(xlsx_error)?;
+        }
+    }
+
+    workbook.save(path).map_err(xlsx_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_cell_to_string_keeps_digits() {
+        assert_eq!(cell_to_string(&Data::Float(123456789012345680.0)), "123456789012345680");
+        assert_eq!(cell_to_string(&Data::Int(42)), "42");
+        assert_eq!(cell_to_string(&Data::String(" 001 ".to_string())), "001");
+    }
+
+    #[test]
+    fn test_write_then_read() {
+        let temp = TempDir::new("xlsx");
+        let path = temp.path().join("result.xlsx");
+        let columns = vec![
+            XlsxColumn {
+                header: "原始ID".to_string(),
+                content: "12345678901234567890\n2".to_string(),
+            },
+            XlsxColumn {
+                header: "加密ID".to_string(),
+                content: "abc\ndef".to_string(),
+            },
+        ];
+        write_columns(&path, "结果", &columns).unwrap();
+        assert_eq!(list_sheets(&path).unwrap(), vec!["结果".to_string()]);
+
+        let options = XlsxImportOptions {
+            header: HeaderMode::Yes,
+            column: ColumnRef::Name("原始ID".to_string()),
+            ..XlsxImportOptions::default()
+        };
+        assert_eq!(read_column(&path, &options).unwrap(), "12345678901234567890\n2");
+    }
+
+    #[test]
+    fn test_blank_lines_keep_rows_aligned() {
+        let temp = TempDir::new("xlsx-align");
+        let path = temp.path().join("result.xlsx");
+        let columns = vec![
+            XlsxColumn {
+                header: "ID".to_string(),
+                content: "1\n\n3".to_string(),
+            },
+            XlsxColumn {
+                header: "备注".to_string(),
+                content: "a,b\nc\nd".to_string(),
+            },
+        ];
+        write_columns(&path, "", &columns).unwrap();
+
+        let mut workbook = open_workbook_auto(&path).unwrap();
+        let sheet = workbook.sheet_names()[0].clone();
+        let range = workbook.worksheet_range(&sheet).unwrap();
+        let rows: Vec<Vec<String>> = range.rows().map(|row| row.iter().map(cell_to_string).collect()).collect();
+        assert_eq!(rows[1], vec!["1", "a,b"]);
+        assert_eq!(rows[2], vec!["", "c"]);
+        assert_eq!(rows[3], vec!["3", "d"]);
+    }
+}
@@ -13,4 +13,393 @@ export interface TextState {
 export interface AppState {
   input: TextState;
   output: TextState;
-} 
\ No newline at end of file
+}
+
+export type SqlDialect = 'mysql' | 'hive' | 'oracle' | 'postgresql';
+
+export type SqlMode = 'in_clause' | 'values' | 'union_all';
+
+export interface SqlOptions {
+  dialect: SqlDialect;
+  mode: SqlMode;
+  column: string;
+  table: string;
+  chunkSize: number;
+  joinWithOr: boolean;
+  // auto: 全部是数字且不超过2^53时不加引号；always: 全部加引号；never: 全部是数字时不加引号
+  quoting: SqlQuoting;
+}
+
+export type SqlQuoting = 'auto' | 'always' | 'never';
+
+export type QuoteStyle =
+  | { style: 'single' }
+  | { style: 'double' }
+  | { style: 'backtick' }
+  | { style: 'custom'; left: string; right: string };
+
+export type QuoteOptions = QuoteStyle & {
+  escape: 'doubling' | 'backslash' | 'none';
+};
+
+export type RecipeStep =
+  | { op: 'sanitize' }
+  | { op: 'replace_commas' }
+  | { op: 'convert_format' }
+  | { op: 'dedupe' }
+  | { op: 'normalize_phones'; options: Partial<PhoneOptions> }
+  | { op: 'encrypt' }
+  | { op: 'decrypt' }
+  | { op: 'add_quotes'; options: QuoteOptions }
+  | { op: 'remove_quotes'; options: QuoteOptions }
+  | { op: 'mask'; options: Partial<MaskOptions> }
+  | { op: 'generate_sql'; options: Partial<SqlOptions> }
+  | { op: 'render_template'; options: Partial<TemplateOptions> };
+
+export interface Recipe {
+  name: string;
+  steps: RecipeStep[];
+}
+
+export interface StepReport {
+  step: string;
+  inputItems: number;
+  outputItems: number;
+}
+
+export interface StepFailure {
+  // 步骤序号，从1开始
+  index: number;
+  step: string;
+  error: string;
+}
+
+// 某一步失败时 steps 只包含之前完成的步骤，output 为失败步骤的输入
+export interface RecipeReport {
+  output: string;
+  steps: StepReport[];
+  failure: StepFailure | null;
+}
+
+export interface TableOptions {
+  format: 'auto' | 'csv' | 'tsv';
+  header: 'auto' | 'yes' | 'no';
+  // 数字为列序号（从1开始），字符串为表头名称
+  column: number | string;
+}
+
+export type ColumnOp =
+  | { op: 'encrypt' }
+  | { op: 'decrypt' }
+  | { op: 'add_quotes'; options: QuoteOptions }
+  | { op: 'remove_quotes'; options: QuoteOptions };
+
+export interface XlsxImportOptions {
+  sheet: string | null;
+  header: 'auto' | 'yes' | 'no';
+  column: number | string;
+}
+
+export interface XlsxColumn {
+  header: string;
+  // 每行一个值，空行导出为空单元格
+  content: string;
+}
+
+export interface PrecisionIssue {
+  line: number;
+  value: string;
+  kind: 'expanded' | 'scientific_notation' | 'precision_loss';
+  message: string;
+}
+
+export interface PrecisionReport {
+  output: string;
+  issues: PrecisionIssue[];
+  refused: number;
+}
+
+export type TextEncoding = 'utf8' | 'utf8_bom' | 'utf16_le' | 'utf16_be' | 'gbk';
+
+export interface DecodedText {
+  content: string;
+  encoding: TextEncoding;
+}
+
+export interface InvisibleChar {
+  code: string;
+  name: string;
+  count: number;
+}
+
+export interface SanitizeLine {
+  line: number;
+  chars: InvisibleChar[];
+}
+
+export interface SanitizeReport {
+  output: string;
+  lines: SanitizeLine[];
+  removed: number;
+}
+
+export type ExtractPattern =
+  | { kind: 'digit_ids'; minLength: number; maxLength: number }
+  | { kind: 'mobile' }
+  | { kind: 'hash' }
+  | { kind: 'uuid' }
+  | { kind: 'oaid' }
+  | { kind: 'custom'; pattern: string };
+
+export interface ExtractOptions {
+  patterns: ExtractPattern[];
+  dedupe: boolean;
+}
+
+export interface InPlaceOptions {
+  mode: 'encrypt' | 'decrypt';
+  minLength: number;
+  maxLength: number;
+}
+
+export interface InPlaceFailure {
+  line: number;
+  value: string;
+  error: string;
+}
+
+export interface InPlaceReport {
+  output: string;
+  replaced: number;
+  failures: InPlaceFailure[];
+}
+
+export type Validator =
+  | { kind: 'id_card' }
+  | { kind: 'imei' }
+  | { kind: 'mobile' }
+  | { kind: 'oaid' }
+  | { kind: 'idfa' }
+  | { kind: 'numeric_id'; minLength: number; maxLength: number };
+
+export interface ValidationLine {
+  line: number;
+  value: string;
+  valid: boolean;
+  detail: string;
+}
+
+export interface ValidationReport {
+  output: string;
+  valid: number;
+  invalid: number;
+  lines: ValidationLine[];
+}
+
+export interface PhoneOptions {
+  format: 'national' | 'e164';
+}
+
+export interface PhoneIssue {
+  line: number;
+  value: string;
+  message: string;
+}
+
+export interface PhoneReport {
+  output: string;
+  normalized: number;
+  issues: PhoneIssue[];
+}
+
+export type MaskRule = 'auto' | 'phone' | 'id_card' | 'email' | 'name' | 'generic';
+
+export interface MaskOptions {
+  rule: MaskRule;
+  maskChar: string;
+  keepFirst: number;
+  keepLast: number;
+}
+
+export interface DuplicateItem {
+  value: string;
+  count: number;
+}
+
+export interface LineItem {
+  line: number;
+  value: string;
+}
+
+export interface ListStats {
+  total: number;
+  unique: number;
+  duplicates: number;
+  topDuplicates: DuplicateItem[];
+  blankLines: number;
+  minLength: number;
+  maxLength: number;
+  nonNumericCount: number;
+  nonNumeric: LineItem[];
+  delimiter: 'newline' | 'comma';
+}
+
+export type SplitMode = { by: 'size'; size: number } | { by: 'count'; count: number };
+
+export type SplitOptions = SplitMode & {
+  prefix?: string;
+  zip?: boolean;
+};
+
+export interface PartInfo {
+  file: string;
+  rows: number;
+  md5: string;
+}
+
+export interface SplitReport {
+  total: number;
+  parts: PartInfo[];
+  zipFile?: string;
+}
+
+export interface TemplateOptions {
+  template: string;
+  delimiter: string;
+}
+
+export type Codec = 'base64' | 'base64_url' | 'url' | 'unicode' | 'hex';
+
+export interface CodecOptions {
+  codec: Codec;
+  direction: 'encode' | 'decode';
+  perLine: boolean;
+}
+
+export interface CodecFailure {
+  line: number;
+  value: string;
+  error: string;
+}
+
+export interface CodecReport {
+  output: string;
+  failures: CodecFailure[];
+}
+
+export interface TimestampOptions {
+  direction: 'to_datetime' | 'to_timestamp';
+  unit: 'auto' | 'seconds' | 'millis' | 'micros';
+  timezone: string;
+  format: string;
+}
+
+export interface TimestampFailure {
+  line: number;
+  value: string;
+  error: string;
+}
+
+export interface TimestampReport {
+  output: string;
+  failures: TimestampFailure[];
+}
+
+export interface SnowflakeOptions {
+  epochMs: number;
+  timestampBits: number;
+  datacenterBits: number;
+  workerBits: number;
+  sequenceBits: number;
+  timezone: string;
+}
+
+export interface SnowflakeId {
+  line: number;
+  id: string;
+  timestamp: number;
+  time: string;
+  datacenter: number;
+  worker: number;
+  sequence: number;
+}
+
+export interface SnowflakeFailure {
+  line: number;
+  value: string;
+  error: string;
+}
+
+export interface SnowflakeReport {
+  output: string;
+  ids: SnowflakeId[];
+  failures: SnowflakeFailure[];
+}
+
+export type NumberBase =
+  | { base: 'decimal' }
+  | { base: 'binary' }
+  | { base: 'hex' }
+  | { base: 'base36' }
+  | { base: 'base62' }
+  | { base: 'custom'; alphabet: string };
+
+export interface RadixOptions {
+  from: NumberBase;
+  to: NumberBase;
+}
+
+export interface RadixFailure {
+  line: number;
+  value: string;
+  error: string;
+}
+
+export interface RadixReport {
+  output: string;
+  failures: RadixFailure[];
+}
+
+// 上传目标的存储后端：阿里云OSS、S3兼容存储或本地文件夹
+export type BackendKind = 'oss' | 's3' | 'local';
+
+export interface OssTarget {
+  name: string;
+  backend: BackendKind;
+  bucket: string;
+  // S3可带协议和端口，如 http://127.0.0.1:9000
+  endpoint: string;
+  keyTemplate: string;
+  signatureVersion: 'v1' | 'v4';
+  // 地域ID，如 cn-hangzhou，V4签名必填；S3为空时使用 us-east-1
+  region: string;
+  // 本地文件夹路径，仅本地文件夹后端使用
+  path: string;
+}
+
+export interface ObjectInfo {
+  key: string;
+  size: number;
+  lastModified?: string;
+}
+
+export type CredentialSecret =
+  | { kind: 'access_key'; accessId: string; accessKey: string }
+  // 过期时间为RFC3339格式，如 2024-01-01T08:00:00Z
+  | { kind: 'sts'; accessId: string; accessKey: string; securityToken: string; expiration: string };
+
+export type Credential = CredentialSecret & {
+  name: string;
+};
+
+export interface CredentialSummary {
+  name: string;
+  kind: string;
+  accessId: string;
+  expiration?: string;
+}
+
+export interface VaultStatus {
+  exists: boolean;
+  unlocked: boolean;
+}
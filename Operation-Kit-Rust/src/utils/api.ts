@@ -1,11 +1,63 @@
 import { invoke } from '@tauri-apps/api/tauri';
+import type { CodecOptions, CodecReport, ColumnOp, Credential, CredentialSummary, DecodedText, ExtractOptions, InPlaceOptions, InPlaceReport, ListStats, MaskOptions, ObjectInfo, OssTarget, PhoneOptions, PhoneReport, PrecisionReport, QuoteOptions, RadixOptions, RadixReport, Recipe, RecipeReport, SanitizeReport, SnowflakeOptions, SnowflakeReport, SplitOptions, SplitReport, SqlOptions, TableOptions, TemplateOptions, TextEncoding, TimestampOptions, TimestampReport, ValidationReport, Validator, VaultStatus, XlsxColumn, XlsxImportOptions } from '../types';
 
-export async function encryptBatch(text: string): Promise<string> {
-  return invoke<string>('process_batch_encrypt', { input: text });
+// 批量加密；末尾全为0的长ID可能被Excel截断，默认拒绝，确认无误时传 allowTrailingZeros 放行
+export async function encryptBatch(text: string, sanitize = true, allowTrailingZeros = false): Promise<string> {
+  return invoke<string>('process_batch_encrypt', { input: text, sanitize, allowTrailingZeros });
 }
 
-export async function decryptBatch(text: string): Promise<string> {
-  return invoke<string>('process_batch_decrypt', { input: text });
+export async function decryptBatch(text: string, sanitize = true): Promise<string> {
+  return invoke<string>('process_batch_decrypt', { input: text, sanitize });
+}
+
+// 解析雪花ID的生成时间、数据中心、机器和序列号
+export async function decodeSnowflake(text: string, options?: Partial<SnowflakeOptions>): Promise<SnowflakeReport> {
+  return invoke<SnowflakeReport>('process_decode_snowflake', { input: text, options });
+}
+
+// 在十进制、二进制、十六进制、36/62进制和自定义字母表之间转换
+export async function convertRadix(text: string, options: RadixOptions): Promise<RadixReport> {
+  return invoke<RadixReport>('process_convert_radix', { input: text, options });
+}
+
+// 清理零宽空格、BOM等不可见字符，并报告每行发现的字符
+export async function sanitizeText(text: string): Promise<SanitizeReport> {
+  return invoke<SanitizeReport>('process_sanitize', { input: text });
+}
+
+// 从聊天记录、日志、JSON等任意文本中提取ID
+export async function extractIds(text: string, options: ExtractOptions): Promise<string> {
+  return invoke<string>('process_extract_ids', { input: text, options });
+}
+
+// 保持原文不变，只把其中的ID原位加密或解密
+export async function replaceInPlace(text: string, options: InPlaceOptions): Promise<InPlaceReport> {
+  return invoke<InPlaceReport>('process_replace_in_place', { input: text, options });
+}
+
+// 按指定规则逐行校验ID
+export async function validateIds(text: string, validator: Validator): Promise<ValidationReport> {
+  return invoke<ValidationReport>('process_validate_ids', { input: text, validator });
+}
+
+// 把手机号规范化为11位或E.164格式
+export async function normalizePhones(text: string, options?: Partial<PhoneOptions>): Promise<PhoneReport> {
+  return invoke<PhoneReport>('process_normalize_phones', { input: text, options });
+}
+
+// 对手机号、身份证、邮箱、姓名等敏感信息脱敏
+export async function maskText(text: string, options?: Partial<MaskOptions>): Promise<string> {
+  return invoke<string>('process_mask', { input: text, options });
+}
+
+// 统计条目数、重复、空行和非数字条目
+export async function listStats(text: string): Promise<ListStats> {
+  return invoke<ListStats>('process_list_stats', { input: text });
+}
+
+// 检查被Excel改成科学计数法或截断精度的ID
+export async function checkPrecision(text: string): Promise<PrecisionReport> {
+  return invoke<PrecisionReport>('process_check_precision', { input: text });
 }
 
 export async function convertFormat(text: string): Promise<string> {
@@ -16,12 +68,92 @@ export async function replaceCommas(text: string): Promise<string> {
   return invoke<string>('process_replace_commas', { input: text });
 }
 
-export async function addQuotes(text: string): Promise<string> {
-  return invoke<string>('process_add_quotes', { input: text });
+export async function addQuotes(text: string, options?: QuoteOptions): Promise<string> {
+  return invoke<string>('process_add_quotes', { input: text, options });
+}
+
+export async function removeQuotes(text: string, options?: QuoteOptions): Promise<string> {
+  return invoke<string>('process_remove_quotes', { input: text, options });
+}
+
+// 将ID列表生成为SQL
+export async function generateSql(text: string, options: Partial<SqlOptions> = {}): Promise<string> {
+  return invoke<string>('process_generate_sql', { input: text, options });
+}
+
+// 对每个条目渲染一次模板，可用 {id} {index} {enc} {dec} {md5}
+export async function renderTemplate(text: string, options: Partial<TemplateOptions>): Promise<string> {
+  return invoke<string>('process_render_template', { input: text, options });
+}
+
+// 从CSV/TSV中提取指定列
+export async function extractColumn(text: string, options: Partial<TableOptions>): Promise<string> {
+  return invoke<string>('process_extract_column', { input: text, options });
+}
+
+// 对CSV/TSV的指定列执行操作并写回整张表
+export async function transformColumn(text: string, options: Partial<TableOptions>, op: ColumnOp): Promise<string> {
+  return invoke<string>('process_transform_column', { input: text, options, op });
+}
+
+// 读取文本文件，自动识别UTF-8/UTF-16/GBK编码
+export async function importTextFile(path: string): Promise<DecodedText> {
+  return invoke<DecodedText>('import_text_file', { path });
+}
+
+// 按指定编码导出文本文件
+export async function exportTextFile(path: string, content: string, encoding: TextEncoding): Promise<void> {
+  return invoke<void>('export_text_file', { path, content, encoding });
+}
+
+// base64/URL/Unicode转义/十六进制编解码
+export async function transformCodec(text: string, options: CodecOptions): Promise<CodecReport> {
+  return invoke<CodecReport>('process_codec', { input: text, options });
+}
+
+// 时间戳与指定时区的格式化时间互转，默认Asia/Shanghai
+export async function convertTimestamps(text: string, options?: Partial<TimestampOptions>): Promise<TimestampReport> {
+  return invoke<TimestampReport>('process_timestamps', { input: text, options });
 }
 
-export async function removeQuotes(text: string): Promise<string> {
-  return invoke<string>('process_remove_quotes', { input: text });
+// 按行数或份数拆分列表，写入目标文件夹或打包为zip
+export async function exportSplitFiles(text: string, dir: string, options: SplitOptions): Promise<SplitReport> {
+  return invoke<SplitReport>('export_split_files', { input: text, dir, options });
+}
+
+// 列出xlsx文件中的工作表
+export async function listXlsxSheets(path: string): Promise<string[]> {
+  return invoke<string[]>('list_xlsx_sheets', { path });
+}
+
+// 从xlsx文件读取指定工作表的指定列
+export async function importXlsxColumn(path: string, options: Partial<XlsxImportOptions>): Promise<string> {
+  return invoke<string>('import_xlsx_column', { path, options });
+}
+
+// 将多列结果导出为xlsx文件
+export async function exportXlsx(path: string, sheet: string, columns: XlsxColumn[]): Promise<void> {
+  return invoke<void>('export_xlsx', { path, sheet, columns });
+}
+
+// 获取所有已保存的配方
+export async function listRecipes(): Promise<Recipe[]> {
+  return invoke<Recipe[]>('list_recipes');
+}
+
+// 保存配方（同名覆盖）
+export async function saveRecipe(recipe: Recipe): Promise<void> {
+  return invoke<void>('save_recipe_config', { recipe });
+}
+
+// 删除配方
+export async function deleteRecipe(name: string): Promise<void> {
+  return invoke<void>('delete_recipe_config', { name });
+}
+
+// 按名称执行配方
+export async function runRecipe(name: string, text: string): Promise<RecipeReport> {
+  return invoke<RecipeReport>('process_run_recipe', { name, input: text });
 }
 
 // 获取当前加密配置
@@ -44,7 +176,68 @@ export async function setHuaweiPrefixConfig(usePrefix: boolean): Promise<void> {
   return invoke<void>('set_huawei_prefix_config', { usePrefix });
 }
 
-// 上传ID列表到OSS
-export async function uploadToOSS(accessId: string, accessKey: string, content: string, channel: string): Promise<string> {
-  return invoke<string>('upload_to_oss', { accessId, accessKey, content, channel });
+// 获取所有上传目标
+export async function listOssTargets(): Promise<OssTarget[]> {
+  return invoke<OssTarget[]>('list_oss_targets');
+}
+
+// 保存上传目标（同名覆盖）
+export async function saveOssTarget(target: OssTarget): Promise<void> {
+  return invoke<void>('save_oss_target', { target });
+}
+
+// 删除上传目标
+export async function deleteOssTarget(name: string): Promise<void> {
+  return invoke<void>('delete_oss_target', { name });
+}
+
+// 获取凭证保险库状态
+export async function getVaultStatus(): Promise<VaultStatus> {
+  return invoke<VaultStatus>('get_vault_status');
+}
+
+// 用主密码解锁保险库，第一次使用时以该密码新建
+export async function unlockVault(password: string): Promise<void> {
+  return invoke<void>('unlock_credential_vault', { password });
+}
+
+// 锁定保险库
+export async function lockVault(): Promise<void> {
+  return invoke<void>('lock_credential_vault');
+}
+
+// 获取已保存的凭证（不含密钥）
+export async function listCredentials(): Promise<CredentialSummary[]> {
+  return invoke<CredentialSummary[]>('list_saved_credentials');
+}
+
+// 保存凭证（同名覆盖）
+export async function saveCredential(credential: Credential): Promise<void> {
+  return invoke<void>('save_credential_config', { credential });
+}
+
+// 删除凭证
+export async function deleteCredential(name: string): Promise<void> {
+  return invoke<void>('delete_credential_config', { name });
+}
+
+// 上传ID列表到指定目标；OSS和S3使用保险库中的凭证，本地文件夹不需要凭证
+// 末尾全为0的长ID默认拒绝，确认无误时传 allowTrailingZeros 放行
+export async function uploadToStorage(credential: string | null, content: string, channel: string, target: string, allowTrailingZeros = false): Promise<string> {
+  return invoke<string>('upload_to_storage', { credential, content, channel, target, allowTrailingZeros });
+}
+
+// 列出目标中以prefix开头的对象
+export async function listStorageObjects(credential: string | null, target: string, prefix = ''): Promise<ObjectInfo[]> {
+  return invoke<ObjectInfo[]>('list_storage_objects', { credential, target, prefix });
+}
+
+// 读取目标中的对象文本
+export async function readStorageObject(credential: string | null, target: string, key: string): Promise<string> {
+  return invoke<string>('read_storage_object', { credential, target, key });
+}
+
+// 删除目标中的对象
+export async function deleteStorageObject(credential: string | null, target: string, key: string): Promise<void> {
+  return invoke<void>('delete_storage_object', { credential, target, key });
 } 
\ No newline at end of file